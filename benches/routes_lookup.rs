@@ -0,0 +1,57 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use my_http_server::HttpPath;
+use my_http_server_controllers::controllers::{HttpRoute, HttpRoutesTree};
+
+fn create_routes(amount: usize) -> Vec<HttpRoute> {
+    let mut result = Vec::with_capacity(amount * 3);
+
+    for i in 0..amount {
+        result.push(HttpRoute::new(format!("/api/v1/entity{}", i).as_str()));
+        result.push(HttpRoute::new(format!("/api/v1/entity{}/{{id}}", i).as_str()));
+        result.push(HttpRoute::new(
+            format!("/api/v1/entity{}/{{id}}/items/{{item_id}}", i).as_str(),
+        ));
+    }
+
+    result
+}
+
+fn create_tree(routes: &[HttpRoute]) -> HttpRoutesTree {
+    let mut tree = HttpRoutesTree::new();
+
+    for (index, route) in routes.iter().enumerate() {
+        tree.insert(route, index);
+    }
+
+    tree
+}
+
+fn routes_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("routes_lookup");
+
+    for amount in [10, 100, 500] {
+        let routes = create_routes(amount);
+        let tree = create_tree(&routes);
+
+        let path = HttpPath::from_str(
+            format!("/api/v1/entity{}/15/items/16", amount - 1).as_str(),
+        );
+
+        group.bench_with_input(BenchmarkId::new("linear_scan", amount), &path, |b, path| {
+            b.iter(|| {
+                routes
+                    .iter()
+                    .position(|route| route.is_my_path(black_box(path)))
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("tree", amount), &path, |b, path| {
+            b.iter(|| tree.find(black_box(path)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, routes_lookup);
+criterion_main!(benches);
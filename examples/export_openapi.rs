@@ -0,0 +1,93 @@
+//! Writes OpenApi spec of the controllers to the file without starting the server.
+//!
+//! cargo run --example export_openapi -- openapi.yaml
+
+use std::sync::Arc;
+
+use my_http_server::{HttpContext, HttpFailResult, HttpOkResult, HttpOutput, WebContentType};
+use my_http_server_controllers::{
+    controllers::{
+        actions::{GetAction, GetDescription, HandleHttpRequest},
+        documentation::{
+            data_types::{HttpDataType, HttpSimpleType},
+            in_parameters::HttpParameters,
+            out_results::HttpResult,
+            HttpActionDescription, ShouldBeAuthorized,
+        },
+        ControllersMiddleware, HttpRoute,
+    },
+    swagger::{OpenApiExportOptions, OpenApiFormat},
+};
+
+struct PingAction;
+
+impl GetAction for PingAction {
+    fn get_route(&self) -> &str {
+        "/api/ping"
+    }
+
+    fn get_model_routes(&self) -> Option<Vec<&'static str>> {
+        None
+    }
+}
+
+impl GetDescription for PingAction {
+    fn get_description(&self) -> Option<HttpActionDescription> {
+        Some(HttpActionDescription {
+            controller_name: "Monitoring",
+            summary: "Ping",
+            description: "Returns pong if service is alive",
+            input_params: HttpParameters::new(None),
+            results: vec![HttpResult {
+                http_code: 200,
+                nullable: false,
+                description: "Service is alive".to_string(),
+                data_type: HttpDataType::SimpleType(HttpSimpleType::String),
+            }],
+            should_be_authorized: ShouldBeAuthorized::No,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl HandleHttpRequest for PingAction {
+    async fn handle_request(
+        &self,
+        _http_route: &HttpRoute,
+        _ctx: &mut HttpContext,
+    ) -> Result<HttpOkResult, HttpFailResult> {
+        HttpOutput::Content {
+            headers: None,
+            content_type: Some(WebContentType::Text),
+            content: "pong".as_bytes().to_vec(),
+        }
+        .into_ok_result(false)
+    }
+}
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "openapi.yaml".to_string());
+
+    let controllers = ControllersMiddleware::new(None, None);
+
+    controllers
+        .register_get_action(Arc::new(PingAction))
+        .unwrap();
+
+    let format = if path.ends_with(".json") {
+        OpenApiFormat::Json
+    } else {
+        OpenApiFormat::Yaml
+    };
+
+    let options = OpenApiExportOptions::new("Example api", env!("CARGO_PKG_VERSION"))
+        .with_server("http://localhost:8000")
+        .with_format(format);
+
+    my_http_server_controllers::swagger::export_openapi_to_file(&controllers, &options, &path)
+        .unwrap();
+
+    println!("OpenApi spec is written to {}", path);
+}
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use my_http_server::{HttpContext, HttpFailResult, HttpOkResult};
+
+use super::{actions::HttpAction, HttpRoute};
+
+#[async_trait::async_trait]
+pub trait ActionFilter {
+    /// Executed after authorization succeeded. Returning error skips the action.
+    async fn before(
+        &self,
+        _http_route: &HttpRoute,
+        _ctx: &mut HttpContext,
+    ) -> Result<(), HttpFailResult> {
+        Ok(())
+    }
+
+    async fn after(&self, _result: &mut Result<HttpOkResult, HttpFailResult>) {}
+}
+
+/// Global filters are executed first, then action filters. `after` hooks are executed in reverse order
+/// and only for filters which `before` hook was executed successfully.
+pub async fn execute_action_with_filters(
+    action: &HttpAction,
+    global_filters: &[Arc<dyn ActionFilter + Send + Sync + 'static>],
+    ctx: &mut HttpContext,
+) -> Result<HttpOkResult, HttpFailResult> {
+    if global_filters.len() == 0 && action.filters.len() == 0 {
+        return action.handler.handle_request(&action.http_route, ctx).await;
+    }
+
+    let filters: Vec<&Arc<dyn ActionFilter + Send + Sync + 'static>> =
+        global_filters.iter().chain(action.filters.iter()).collect();
+
+    let mut executed_filters = 0;
+    let mut before_result = Ok(());
+
+    for filter in &filters {
+        before_result = filter.before(&action.http_route, ctx).await;
+
+        if before_result.is_err() {
+            break;
+        }
+
+        executed_filters += 1;
+    }
+
+    let mut result = match before_result {
+        Ok(()) => action.handler.handle_request(&action.http_route, ctx).await,
+        Err(err) => Err(err),
+    };
+
+    for filter in filters[..executed_filters].iter().rev() {
+        filter.after(&mut result).await;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use hyper::Method;
+    use my_http_server::{HttpPath, WebContentType};
+
+    use super::*;
+    use crate::controllers::{
+        test_utils::{create_http_context, TestHandler},
+        ControllersMiddleware,
+    };
+
+    struct RecordingFilter {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        fail_before: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl ActionFilter for RecordingFilter {
+        async fn before(
+            &self,
+            _http_route: &HttpRoute,
+            _ctx: &mut HttpContext,
+        ) -> Result<(), HttpFailResult> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("before:{}", self.name));
+
+            if self.fail_before {
+                return Err(HttpFailResult::new(
+                    WebContentType::Text,
+                    429,
+                    "Too many requests".as_bytes().to_vec(),
+                    false,
+                    true,
+                ));
+            }
+
+            Ok(())
+        }
+
+        async fn after(&self, result: &mut Result<HttpOkResult, HttpFailResult>) {
+            let result = if result.is_ok() { "ok" } else { "err" };
+
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("after:{}:{}", self.name, result));
+        }
+    }
+
+    fn create_filter(
+        name: &'static str,
+        log: &Arc<Mutex<Vec<String>>>,
+        fail_before: bool,
+    ) -> Arc<dyn ActionFilter + Send + Sync + 'static> {
+        Arc::new(RecordingFilter {
+            name,
+            log: log.clone(),
+            fail_before,
+        })
+    }
+
+    fn create_controllers(
+        log: &Arc<Mutex<Vec<String>>>,
+        group_filter_fails: bool,
+        handler: Arc<TestHandler>,
+    ) -> ControllersMiddleware {
+        let mut controllers = ControllersMiddleware::new(None, None);
+        controllers.add_global_action_filter(create_filter("global", log, false));
+
+        controllers
+            .create_route_group("/api")
+            .with_filter(create_filter("group", log, group_filter_fails))
+            .register_get_action(handler)
+            .unwrap();
+
+        controllers
+            .add_action_filter(
+                &Method::GET,
+                "/api/items",
+                create_filter("action", log, false),
+            )
+            .unwrap();
+
+        controllers
+    }
+
+    async fn execute(controllers: &ControllersMiddleware) -> Result<HttpOkResult, HttpFailResult> {
+        let actions = controllers.get_actions();
+        let action = actions
+            .get
+            .find_action(&HttpPath::from_str("/api/items"))
+            .unwrap();
+
+        let mut ctx = create_http_context(Method::GET, "/api/items", "127.0.0.1", &[]);
+        execute_action_with_filters(action, &controllers.action_filters, &mut ctx).await
+    }
+
+    #[tokio::test]
+    async fn test_filters_are_executed_global_group_action() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(TestHandler::new("items").with_route("/items"));
+        let controllers = create_controllers(&log, false, handler.clone());
+
+        assert!(execute(&controllers).await.is_ok());
+        assert_eq!(1, handler.get_calls());
+
+        assert_eq!(
+            vec![
+                "before:global",
+                "before:group",
+                "before:action",
+                "after:action:ok",
+                "after:group:ok",
+                "after:global:ok",
+            ],
+            *log.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_before_skips_the_action() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(TestHandler::new("items").with_route("/items"));
+        let controllers = create_controllers(&log, true, handler.clone());
+
+        let result = execute(&controllers).await;
+
+        assert_eq!(429, result.err().unwrap().status_code);
+        assert_eq!(0, handler.get_calls());
+
+        // Only filter which before succeeded gets after, and it sees the error
+        assert_eq!(
+            vec!["before:global", "before:group", "after:global:err"],
+            *log.lock().unwrap()
+        );
+    }
+}
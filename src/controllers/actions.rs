@@ -1,10 +1,11 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use my_http_server::{HttpContext, HttpFailResult, HttpOkResult};
+use my_http_server::{HttpContext, HttpFailResult, HttpOkResult, HttpPath, WebContentType};
 
 use super::{
     documentation::{HttpActionDescription, ShouldBeAuthorized},
-    AuthErrorFactory, AuthorizationMap, HttpRoute,
+    ActionFilter, AuthErrorFactory, AuthorizationFailContext, AuthorizationMap,
+    AuthorizationPolicy, HttpRoute, HttpRoutesTree, IpFilter, TrustedProxies,
 };
 
 pub trait GetAction {
@@ -27,6 +28,21 @@ pub trait DeleteAction {
     fn get_model_routes(&self) -> Option<Vec<&'static str>>;
 }
 
+pub trait PatchAction {
+    fn get_route(&self) -> &str;
+    fn get_model_routes(&self) -> Option<Vec<&'static str>>;
+}
+
+pub trait HeadAction {
+    fn get_route(&self) -> &str;
+    fn get_model_routes(&self) -> Option<Vec<&'static str>>;
+}
+
+pub trait OptionsAction {
+    fn get_route(&self) -> &str;
+    fn get_model_routes(&self) -> Option<Vec<&'static str>>;
+}
+
 #[async_trait::async_trait]
 pub trait HandleHttpRequest {
     async fn handle_request(
@@ -42,89 +58,476 @@ pub trait GetDescription {
 
 pub trait GetShouldBeAuthorized {
     fn get_should_be_authorized(&self) -> &ShouldBeAuthorized;
+
+    fn get_authentication_schemes(&self) -> Option<&[String]> {
+        None
+    }
+
+    fn get_required_permissions(&self) -> &[String] {
+        &[]
+    }
 }
 
+#[derive(Clone)]
 pub struct HttpAction {
     pub handler: Arc<dyn HandleHttpRequest + Send + Sync + 'static>,
     pub http_route: HttpRoute,
     pub description: Arc<dyn GetDescription + Send + Sync + 'static>,
     pub should_be_authorized: ShouldBeAuthorized,
+    pub action_type: &'static str,
+    pub swagger_tag: Option<String>,
+    pub filters: Vec<Arc<dyn ActionFilter + Send + Sync + 'static>>,
+    pub authentication_schemes: Option<Vec<String>>,
+    pub policies: Vec<Arc<dyn AuthorizationPolicy + Send + Sync + 'static>>,
+    pub required_permissions: Vec<String>,
+    pub ip_filter: Option<IpFilter>,
+    /// Media types which override the ones resolved by the result data type. Key is http code
+    pub result_content_types: HashMap<u16, String>,
+    /// Disabled action keeps its route and settings, but it is neither served nor described
+    pub enabled: bool,
 }
 
 impl GetShouldBeAuthorized for HttpAction {
     fn get_should_be_authorized(&self) -> &ShouldBeAuthorized {
         &self.should_be_authorized
     }
+
+    fn get_authentication_schemes(&self) -> Option<&[String]> {
+        self.authentication_schemes.as_deref()
+    }
+
+    fn get_required_permissions(&self) -> &[String] {
+        &self.required_permissions
+    }
 }
 
+#[derive(Clone)]
 pub struct HttpActions {
-    actions: Vec<HttpAction>,
+    actions: Vec<Arc<HttpAction>>,
+    routes_tree: HttpRoutesTree,
 }
 
 impl HttpActions {
     pub fn new() -> Self {
         Self {
             actions: Vec::new(),
+            routes_tree: HttpRoutesTree::new(),
         }
     }
 
     pub fn register(&mut self, action: HttpAction) -> Result<(), String> {
         for registered_action in &self.actions {
-            if registered_action.http_route.route.to_lowercase()
-                == action.http_route.route.to_lowercase()
+            if registered_action
+                .http_route
+                .is_conflicting_with(&action.http_route)
             {
                 return Err(format!(
-                    "Route {} is already registered",
-                    action.http_route.route
+                    "Route {} of action {} conflicts with route {} of action {}",
+                    action.http_route.route,
+                    action.action_type,
+                    registered_action.http_route.route,
+                    registered_action.action_type
                 ));
             }
         }
 
-        self.actions.push(action);
+        self.routes_tree.insert(&action.http_route, self.actions.len());
+        self.actions.push(Arc::new(action));
 
         Ok(())
     }
 
+    pub fn unregister(&mut self, route: &str) -> Option<Arc<HttpAction>> {
+        let index = self
+            .actions
+            .iter()
+            .position(|action| action.http_route.route.eq_ignore_ascii_case(route))?;
+
+        let removed = self.actions.remove(index);
+        self.rebuild_routes_tree();
+
+        Some(removed)
+    }
+
+    /// Route of disabled action still conflicts with new routes, so it can be enabled back at any time
+    pub fn set_enabled(&mut self, route: &str, enabled: bool) -> Option<()> {
+        self.find_action_by_route_mut(route)?.enabled = enabled;
+        self.rebuild_routes_tree();
+
+        Some(())
+    }
+
+    fn rebuild_routes_tree(&mut self) {
+        self.routes_tree = HttpRoutesTree::new();
+        for (index, action) in self.actions.iter().enumerate() {
+            if action.enabled {
+                self.routes_tree.insert(&action.http_route, index);
+            }
+        }
+    }
+
     pub async fn handle_request(
         &self,
         ctx: &mut HttpContext,
         authorization_map: &AuthorizationMap,
         auth_error_factory: &Option<Arc<dyn AuthErrorFactory + Send + Sync + 'static>>,
+        global_filters: &[Arc<dyn ActionFilter + Send + Sync + 'static>],
+        trusted_proxies: &TrustedProxies,
     ) -> Option<Result<HttpOkResult, HttpFailResult>> {
-        for action in &self.actions {
-            if action.http_route.is_my_path(&ctx.request.http_path) {
-                match authorization_map.is_authorized(
-                    action,
-                    &ctx.credentials,
-                    ctx.request.get_ip().get_real_ip(),
-                ) {
-                    super::AuthorizationResult::Allowed => {
-                        return Some(action.handler.handle_request(&action.http_route, ctx).await);
-                    }
-                    super::AuthorizationResult::NotAuthenticated => {
-                        if let Some(result) = auth_error_factory {
-                            return Some(Err(result.get_not_authenticated()));
-                        } else {
-                            return Some(Err(HttpFailResult::as_unauthorized(Some(
-                                "No session credentials are found".to_string(),
-                            ))));
-                        }
-                    }
-                    super::AuthorizationResult::NotAuthorized(claim_name) => {
-                        if let Some(result) = auth_error_factory {
-                            return Some(Err(result.get_not_authorized(claim_name)));
-                        } else {
-                            return Some(Err(HttpFailResult::as_unauthorized(None)));
-                        }
-                    }
+        let action = self.find_action(&ctx.request.http_path)?;
+
+        if let Some(ip_filter) = &action.ip_filter {
+            let client_ip = trusted_proxies.resolve_request_ip(&ctx.request);
+
+            if !ip_filter.is_ip_allowed(&client_ip) {
+                return Some(Err(HttpFailResult::new(
+                    WebContentType::Text,
+                    403,
+                    format!("Ip {} is not allowed", client_ip).into_bytes(),
+                    false,
+                    true,
+                )));
+            }
+        }
+
+        let fail_context = AuthorizationFailContext {
+            method: ctx.request.get_method(),
+            route: action.http_route.route.as_str(),
+            action: action.action_type,
+        };
+
+        if let Err(err) = authorization_map
+            .authorize(
+                action,
+                &ctx.credentials,
+                ctx.request.get_ip().get_real_ip(),
+                &fail_context,
+                auth_error_factory,
+            )
+            .await
+        {
+            return Some(Err(err));
+        }
+
+        if action.policies.len() > 0 {
+            let route_values = action.http_route.get_values(&ctx.request.http_path);
+
+            for policy in &action.policies {
+                let result = policy
+                    .authorize(&action.http_route, &route_values, &ctx.credentials)
+                    .await;
+
+                if let Err(err) = result.into_http_result(&fail_context, auth_error_factory) {
+                    return Some(Err(err));
                 }
             }
         }
 
-        None
+        Some(super::execute_action_with_filters(action, global_filters, ctx).await)
+    }
+
+    pub fn find_action_by_route_mut(&mut self, route: &str) -> Option<&mut HttpAction> {
+        let action = self
+            .actions
+            .iter_mut()
+            .find(|action| action.http_route.route.eq_ignore_ascii_case(route))?;
+
+        Some(Arc::make_mut(action))
+    }
+
+    pub fn find_action(&self, path: &HttpPath) -> Option<&HttpAction> {
+        let index = self.routes_tree.find(path)?;
+        let action = self.actions.get(index)?;
+
+        if !action.enabled {
+            return None;
+        }
+
+        Some(action.as_ref())
     }
 
-    pub fn get_actions(&self) -> &Vec<HttpAction> {
+    pub fn get_actions(&self) -> &Vec<Arc<HttpAction>> {
         &self.actions
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use hyper::Method;
+    use my_http_server::{RequestClaim, RequestCredentials};
+    use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+    use super::*;
+    use crate::controllers::{
+        documentation::out_results::HttpResult,
+        middleware::create_http_action,
+        test_utils::{create_http_context, TestHandler},
+        AuthorizationDenialReason, AuthorizationPolicy, AuthorizationResult, HttpRouteValues,
+        RequiredClaims,
+    };
+
+    fn create_actions(handler: Arc<TestHandler>, ip_filter: Option<IpFilter>) -> HttpActions {
+        let mut http_action =
+            create_http_action(&Method::GET, HttpRoute::new("/metrics"), None, handler).unwrap();
+
+        http_action.should_be_authorized = ShouldBeAuthorized::No;
+        http_action.ip_filter = ip_filter;
+
+        let mut actions = HttpActions::new();
+        actions.register(http_action).unwrap();
+        actions
+    }
+
+    #[tokio::test]
+    async fn test_ip_filter_uses_forwarded_ip_of_trusted_proxy_only() {
+        let handler = Arc::new(TestHandler::new("metrics"));
+        let actions = create_actions(
+            handler.clone(),
+            IpFilter::new().allow("10.0.0.0/8").unwrap().into(),
+        );
+
+        let authorization_map = AuthorizationMap::new(None);
+        let trusted_proxies = TrustedProxies::new().add("172.16.0.0/12").unwrap();
+
+        // Client sets X-Forwarded-For by itself
+        let mut ctx = create_http_context(
+            Method::GET,
+            "/metrics",
+            "8.8.8.8",
+            &[("X-Forwarded-For", "10.0.0.1")],
+        );
+
+        let result = actions
+            .handle_request(&mut ctx, &authorization_map, &None, &[], &trusted_proxies)
+            .await
+            .unwrap();
+
+        assert_eq!(403, result.err().unwrap().status_code);
+        assert_eq!(0, handler.get_calls());
+
+        // Client forges the chain, trusted proxy appends the real client ip
+        let mut ctx = create_http_context(
+            Method::GET,
+            "/metrics",
+            "172.16.0.5",
+            &[("X-Forwarded-For", "10.0.0.1, 8.8.4.4")],
+        );
+
+        let result = actions
+            .handle_request(&mut ctx, &authorization_map, &None, &[], &trusted_proxies)
+            .await
+            .unwrap();
+
+        assert_eq!(403, result.err().unwrap().status_code);
+        assert_eq!(0, handler.get_calls());
+
+        let mut ctx = create_http_context(
+            Method::GET,
+            "/metrics",
+            "172.16.0.5",
+            &[
+                ("X-Forwarded-For", "8.8.4.4"),
+                ("X-Forwarded-For", "10.0.0.1"),
+            ],
+        );
+
+        let result = actions
+            .handle_request(&mut ctx, &authorization_map, &None, &[], &trusted_proxies)
+            .await
+            .unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(1, handler.get_calls());
+    }
+
+    struct IpRestrictedCredentials {
+        allowed_ips: Vec<String>,
+    }
+
+    impl RequestCredentials for IpRestrictedCredentials {
+        fn get_id(&self) -> &str {
+            "test"
+        }
+
+        fn get_claims(&self) -> Option<Vec<RequestClaim>> {
+            Some(vec![RequestClaim {
+                id: "admin",
+                expires: DateTimeAsMicroseconds::new(
+                    DateTimeAsMicroseconds::now().unix_microseconds + 60_000_000,
+                ),
+                allowed_ips: Some(&self.allowed_ips),
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claims_are_checked_against_real_ip_of_request() {
+        let handler = Arc::new(TestHandler::new("reports"));
+
+        let mut http_action = create_http_action(
+            &Method::GET,
+            HttpRoute::new("/reports"),
+            None,
+            handler.clone(),
+        )
+        .unwrap();
+
+        http_action.should_be_authorized =
+            ShouldBeAuthorized::YesWithClaims(RequiredClaims::from_slice_of_str(&["admin"]));
+
+        let mut actions = HttpActions::new();
+        actions.register(http_action).unwrap();
+
+        let authorization_map = AuthorizationMap::new(None);
+
+        // No trusted proxies are configured - claims still see the ip forwarded by the load balancer
+        let trusted_proxies = TrustedProxies::new();
+
+        let mut ctx = create_http_context(
+            Method::GET,
+            "/reports",
+            "172.16.0.5",
+            &[("X-Forwarded-For", "10.0.0.1")],
+        );
+        ctx.credentials = Some(Box::new(IpRestrictedCredentials {
+            allowed_ips: vec!["10.0.0.1".to_string()],
+        }));
+
+        let result = actions
+            .handle_request(&mut ctx, &authorization_map, &None, &[], &trusted_proxies)
+            .await
+            .unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(1, handler.get_calls());
+
+        let mut ctx = create_http_context(Method::GET, "/reports", "172.16.0.5", &[]);
+        ctx.credentials = Some(Box::new(IpRestrictedCredentials {
+            allowed_ips: vec!["10.0.0.1".to_string()],
+        }));
+
+        let result = actions
+            .handle_request(&mut ctx, &authorization_map, &None, &[], &trusted_proxies)
+            .await
+            .unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(1, handler.get_calls());
+    }
+
+    struct OwnItemPolicy;
+
+    #[async_trait::async_trait]
+    impl AuthorizationPolicy for OwnItemPolicy {
+        async fn authorize(
+            &self,
+            _http_route: &HttpRoute,
+            route_values: &HttpRouteValues<'_>,
+            _request_credentials: &Option<Box<dyn RequestCredentials + Send + Sync + 'static>>,
+        ) -> AuthorizationResult {
+            if route_values.get("id") == Some("own") {
+                return AuthorizationResult::Allowed;
+            }
+
+            AuthorizationResult::NotAuthorized(AuthorizationDenialReason::PolicyDenied(
+                "Item belongs to another user".to_string(),
+            ))
+        }
+    }
+
+    struct AuthErrorFactoryMock {
+        denials: Mutex<Vec<String>>,
+    }
+
+    impl AuthErrorFactory for AuthErrorFactoryMock {
+        fn get_not_authenticated(&self) -> HttpFailResult {
+            HttpFailResult::as_unauthorized(None)
+        }
+
+        fn get_not_authorized(
+            &self,
+            reason: AuthorizationDenialReason,
+            ctx: &AuthorizationFailContext,
+        ) -> HttpFailResult {
+            let reason = match reason {
+                AuthorizationDenialReason::PolicyDenied(reason) => reason,
+                _ => panic!("Policy denial is expected"),
+            };
+
+            self.denials
+                .lock()
+                .unwrap()
+                .push(format!("{} {}: {}", ctx.method, ctx.route, reason));
+
+            HttpFailResult::new(WebContentType::Text, 403, reason.into_bytes(), false, true)
+        }
+
+        fn get_global_http_fail_result_types(&self) -> Option<Vec<HttpResult>> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_policy_denial_goes_through_auth_error_factory() {
+        let handler = Arc::new(TestHandler::new("item"));
+
+        let mut http_action = create_http_action(
+            &Method::GET,
+            HttpRoute::new("/items/{id}"),
+            None,
+            handler.clone(),
+        )
+        .unwrap();
+
+        http_action.should_be_authorized = ShouldBeAuthorized::No;
+        http_action.policies.push(Arc::new(OwnItemPolicy));
+
+        let mut actions = HttpActions::new();
+        actions.register(http_action).unwrap();
+
+        let authorization_map = AuthorizationMap::new(None);
+        let factory = Arc::new(AuthErrorFactoryMock {
+            denials: Mutex::new(Vec::new()),
+        });
+        let auth_error_factory: Option<Arc<dyn AuthErrorFactory + Send + Sync + 'static>> =
+            Some(factory.clone());
+
+        let mut ctx = create_http_context(Method::GET, "/items/foreign", "127.0.0.1", &[]);
+
+        let result = actions
+            .handle_request(
+                &mut ctx,
+                &authorization_map,
+                &auth_error_factory,
+                &[],
+                &TrustedProxies::new(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(403, result.err().unwrap().status_code);
+        assert_eq!(0, handler.get_calls());
+        assert_eq!(
+            vec!["GET /items/{id}: Item belongs to another user"],
+            *factory.denials.lock().unwrap()
+        );
+
+        let mut ctx = create_http_context(Method::GET, "/items/own", "127.0.0.1", &[]);
+
+        let result = actions
+            .handle_request(
+                &mut ctx,
+                &authorization_map,
+                &auth_error_factory,
+                &[],
+                &TrustedProxies::new(),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(1, handler.get_calls());
+    }
+}
@@ -0,0 +1,68 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApiKeyIn {
+    Header,
+    Query,
+    Cookie,
+}
+
+impl ApiKeyIn {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyIn::Header => "header",
+            ApiKeyIn::Query => "query",
+            ApiKeyIn::Cookie => "cookie",
+        }
+    }
+}
+
+/// Where the client sends the api key. To accept keys from several locations -
+/// register several ApiKeys schemes with different scheme names.
+#[derive(Debug, Clone)]
+pub struct ApiKeyLocation {
+    pub scheme_name: String,
+    pub key_in: ApiKeyIn,
+    pub name: String,
+    /// Value prefix. Example: `ApiKey` for `Authorization: ApiKey {key}`
+    pub prefix: Option<String>,
+}
+
+impl ApiKeyLocation {
+    pub const DEFAULT_SCHEME_NAME: &'static str = "ApiKeyAuth";
+
+    pub fn new(key_in: ApiKeyIn, name: &str) -> Self {
+        Self {
+            scheme_name: Self::DEFAULT_SCHEME_NAME.to_string(),
+            key_in,
+            name: name.to_string(),
+            prefix: None,
+        }
+    }
+
+    pub fn header(name: &str) -> Self {
+        Self::new(ApiKeyIn::Header, name)
+    }
+
+    pub fn query(name: &str) -> Self {
+        Self::new(ApiKeyIn::Query, name)
+    }
+
+    pub fn cookie(name: &str) -> Self {
+        Self::new(ApiKeyIn::Cookie, name)
+    }
+
+    pub fn with_scheme_name(mut self, scheme_name: &str) -> Self {
+        self.scheme_name = scheme_name.to_string();
+        self
+    }
+
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+}
+
+impl Default for ApiKeyLocation {
+    fn default() -> Self {
+        Self::header("X-API-Key")
+    }
+}
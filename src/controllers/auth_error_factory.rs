@@ -1,9 +1,16 @@
 use my_http_server::HttpFailResult;
 
-use super::documentation::out_results::HttpResult;
+use super::{
+    documentation::out_results::HttpResult, AuthorizationDenialReason, AuthorizationFailContext,
+};
 
 pub trait AuthErrorFactory {
     fn get_not_authenticated(&self) -> HttpFailResult;
-    fn get_not_authorized(&self, claim_name: String) -> HttpFailResult;
+    fn get_not_authorized(
+        &self,
+        reason: AuthorizationDenialReason,
+        ctx: &AuthorizationFailContext,
+    ) -> HttpFailResult;
+
     fn get_global_http_fail_result_types(&self) -> Option<Vec<HttpResult>>;
 }
@@ -0,0 +1,11 @@
+use my_http_server::RequestCredentials;
+
+/// RequestCredentials do not carry the scheme they were authenticated with.
+/// Authentication middleware which creates the credentials knows it and reports it here.
+pub trait AuthenticationSchemeResolver {
+    /// Name of the scheme as it is registered. Example: `BearerAuth`, `ApiKeyAuth`
+    fn get_scheme_name(
+        &self,
+        request_credentials: &(dyn RequestCredentials + Send + Sync + 'static),
+    ) -> Option<String>;
+}
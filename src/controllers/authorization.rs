@@ -1,21 +1,42 @@
-use super::RequiredClaims;
+use super::{ApiKeyLocation, OAuth2Settings, OpenIdConnectSettings, RequiredClaims};
 
 pub enum ControllersAuthorization {
     BasicAuthentication {
         global: bool,
         global_claims: RequiredClaims,
     },
+    /// Use [`ControllersAuthorization::api_keys`] to keep the `X-API-Key` header location of the previous versions
     ApiKeys {
         global: bool,
         global_claims: RequiredClaims,
+        location: ApiKeyLocation,
     },
     BearerAuthentication {
         global: bool,
         global_claims: RequiredClaims,
     },
+    OAuth2 {
+        global: bool,
+        global_claims: RequiredClaims,
+        settings: OAuth2Settings,
+    },
+    OpenIdConnect {
+        global: bool,
+        global_claims: RequiredClaims,
+        settings: OpenIdConnectSettings,
+    },
 }
 
 impl ControllersAuthorization {
+    /// Api keys in `X-API-Key` header with `ApiKeyAuth` scheme name
+    pub fn api_keys(global: bool, global_claims: RequiredClaims) -> Self {
+        ControllersAuthorization::ApiKeys {
+            global,
+            global_claims,
+            location: ApiKeyLocation::default(),
+        }
+    }
+
     pub fn is_global_authorization_enabled(&self) -> bool {
         match self {
             ControllersAuthorization::BasicAuthentication {
@@ -25,11 +46,22 @@ impl ControllersAuthorization {
             ControllersAuthorization::ApiKeys {
                 global,
                 global_claims: _,
+                location: _,
             } => *global,
             ControllersAuthorization::BearerAuthentication {
                 global,
                 global_claims: _,
             } => *global,
+            ControllersAuthorization::OAuth2 {
+                global,
+                global_claims: _,
+                settings: _,
+            } => *global,
+            ControllersAuthorization::OpenIdConnect {
+                global,
+                global_claims: _,
+                settings: _,
+            } => *global,
         }
     }
 
@@ -42,11 +74,22 @@ impl ControllersAuthorization {
             ControllersAuthorization::ApiKeys {
                 global: _,
                 global_claims,
+                location: _,
             } => global_claims,
             ControllersAuthorization::BearerAuthentication {
                 global: _,
                 global_claims,
             } => global_claims,
+            ControllersAuthorization::OAuth2 {
+                global: _,
+                global_claims,
+                settings: _,
+            } => global_claims,
+            ControllersAuthorization::OpenIdConnect {
+                global: _,
+                global_claims,
+                settings: _,
+            } => global_claims,
         }
     }
 
@@ -59,11 +102,31 @@ impl ControllersAuthorization {
             ControllersAuthorization::ApiKeys {
                 global: _,
                 global_claims: _,
-            } => "ApiKeyAuth",
+                location,
+            } => location.scheme_name.as_str(),
             ControllersAuthorization::BearerAuthentication {
                 global: _,
                 global_claims: _,
             } => "BearerAuth",
+            ControllersAuthorization::OAuth2 {
+                global: _,
+                global_claims: _,
+                settings,
+            } => settings.scheme_name.as_str(),
+            ControllersAuthorization::OpenIdConnect {
+                global: _,
+                global_claims: _,
+                settings,
+            } => settings.scheme_name.as_str(),
+        }
+    }
+
+    /// OpenAPI allows to list required scopes only for OAuth2 and OpenID Connect schemes
+    pub fn has_scopes(&self) -> bool {
+        match self {
+            ControllersAuthorization::OAuth2 { .. } => true,
+            ControllersAuthorization::OpenIdConnect { .. } => true,
+            _ => false,
         }
     }
 }
@@ -0,0 +1,46 @@
+use std::fmt::Display;
+
+use hyper::Method;
+
+/// Why the request was not authorized
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthorizationDenialReason {
+    ClaimIsMissing(String),
+    IpIsNotAllowed { claim: String, ip: String },
+    ClaimIsExpired(String),
+    ForbiddenClaims(String),
+    PermissionIsMissing(String),
+    PolicyDenied(String),
+}
+
+impl Display for AuthorizationDenialReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthorizationDenialReason::ClaimIsMissing(claim) => {
+                write!(f, "Claim {} is missing", claim)
+            }
+            AuthorizationDenialReason::IpIsNotAllowed { claim, ip } => {
+                write!(f, "Claim {} is not allowed for ip {}", claim, ip)
+            }
+            AuthorizationDenialReason::ClaimIsExpired(claim) => {
+                write!(f, "Claim {} is expired", claim)
+            }
+            AuthorizationDenialReason::ForbiddenClaims(expression) => {
+                write!(f, "Claims do not satisfy {}", expression)
+            }
+            AuthorizationDenialReason::PermissionIsMissing(permission) => {
+                write!(f, "Permission {} is missing", permission)
+            }
+            AuthorizationDenialReason::PolicyDenied(reason) => {
+                write!(f, "Denied by policy: {}", reason)
+            }
+        }
+    }
+}
+
+/// Action the request was denied for
+pub struct AuthorizationFailContext<'s> {
+    pub method: &'s Method,
+    pub route: &'s str,
+    pub action: &'s str,
+}
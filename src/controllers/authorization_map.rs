@@ -1,13 +1,20 @@
-use my_http_server::RequestCredentials;
+use std::{sync::Arc, time::Duration};
+
+use my_http_server::{HttpFailResult, RequestCredentials};
+use rust_extensions::date_time::DateTimeAsMicroseconds;
 
 use super::{
-    actions::GetShouldBeAuthorized, documentation::ShouldBeAuthorized, ControllersAuthorization,
+    actions::GetShouldBeAuthorized, documentation::ShouldBeAuthorized, AuthErrorFactory,
+    AuthenticationSchemeResolver, AuthorizationDenialReason, AuthorizationFailContext,
+    AuthorizationProvider, CachedAuthorizationProvider, ClaimsAuthorizationProvider,
+    ControllersAuthorization, RbacMap, RequiredClaims,
 };
 
+#[derive(Debug, Clone)]
 pub enum AuthorizationResult {
     Allowed,
     NotAuthenticated,
-    NotAuthorized(String),
+    NotAuthorized(AuthorizationDenialReason),
 }
 
 impl AuthorizationResult {
@@ -34,74 +41,309 @@ impl AuthorizationResult {
             AuthorizationResult::NotAuthorized(_) => true,
         }
     }
+
+    pub fn into_http_result(
+        self,
+        fail_context: &AuthorizationFailContext,
+        auth_error_factory: &Option<Arc<dyn AuthErrorFactory + Send + Sync + 'static>>,
+    ) -> Result<(), HttpFailResult> {
+        match self {
+            AuthorizationResult::Allowed => Ok(()),
+            AuthorizationResult::NotAuthenticated => {
+                if let Some(result) = auth_error_factory {
+                    Err(result.get_not_authenticated())
+                } else {
+                    Err(HttpFailResult::as_unauthorized(Some(
+                        "No session credentials are found".to_string(),
+                    )))
+                }
+            }
+            AuthorizationResult::NotAuthorized(reason) => {
+                if let Some(result) = auth_error_factory {
+                    Err(result.get_not_authorized(reason, fail_context))
+                } else {
+                    Err(HttpFailResult::as_unauthorized(None))
+                }
+            }
+        }
+    }
+}
+
+impl From<AuthorizationDenialReason> for AuthorizationResult {
+    fn from(src: AuthorizationDenialReason) -> Self {
+        AuthorizationResult::NotAuthorized(src)
+    }
 }
 
+/// Security scheme an action accepts together with the claims it requires
+pub struct SecurityRequirement<'s> {
+    pub scheme: &'s ControllersAuthorization,
+    pub claims: RequiredClaims,
+}
+
+/// Holds every authentication scheme the service accepts.
+/// Credentials are validated by the authentication middleware. Here we only check that the scheme is accepted by the action
+/// and which claims are required.
 pub struct AuthorizationMap {
-    pub global_authorization: Option<ControllersAuthorization>,
+    pub schemes: Vec<ControllersAuthorization>,
+    pub provider: Arc<dyn AuthorizationProvider + Send + Sync + 'static>,
+    /// Required if several schemes are registered. Credentials with unknown scheme are not authenticated
+    pub scheme_resolver: Option<Arc<dyn AuthenticationSchemeResolver + Send + Sync + 'static>>,
+    pub rbac: RbacMap,
 }
 
 impl AuthorizationMap {
     pub fn new(global_authorization: Option<ControllersAuthorization>) -> Self {
         Self {
-            global_authorization,
+            schemes: global_authorization.into_iter().collect(),
+            provider: Arc::new(ClaimsAuthorizationProvider),
+            scheme_resolver: None,
+            rbac: RbacMap::new(),
         }
     }
 
+    /// Replaces the default claims check. If cache_ttl is set - allowed results are cached.
+    /// To cache denials or to limit the cache - pass CachedAuthorizationProvider configured the way you need without cache_ttl.
+    pub fn set_provider(
+        &mut self,
+        provider: Arc<dyn AuthorizationProvider + Send + Sync + 'static>,
+        cache_ttl: Option<Duration>,
+    ) {
+        self.provider = match cache_ttl {
+            Some(ttl) => Arc::new(CachedAuthorizationProvider::new(provider, ttl)),
+            None => provider,
+        };
+    }
+
+    pub fn set_scheme_resolver(
+        &mut self,
+        scheme_resolver: Arc<dyn AuthenticationSchemeResolver + Send + Sync + 'static>,
+    ) {
+        self.scheme_resolver = Some(scheme_resolver);
+    }
+
+    pub fn from_schemes(schemes: Vec<ControllersAuthorization>) -> Result<Self, String> {
+        let mut result = Self::new(None);
+
+        for scheme in schemes {
+            result.add_scheme(scheme)?;
+        }
+
+        Ok(result)
+    }
+
+    pub fn add_scheme(&mut self, scheme: ControllersAuthorization) -> Result<(), String> {
+        if self.get_scheme(scheme.as_openid_str()).is_some() {
+            return Err(format!(
+                "Authentication scheme {} is already registered",
+                scheme.as_openid_str()
+            ));
+        }
+
+        self.schemes.push(scheme);
+        Ok(())
+    }
+
+    pub fn get_scheme(&self, name: &str) -> Option<&ControllersAuthorization> {
+        self.schemes
+            .iter()
+            .find(|scheme| scheme.as_openid_str().eq_ignore_ascii_case(name))
+    }
+
+    pub fn check_schemes(&self, names: &[String]) -> Result<(), String> {
+        for name in names {
+            if self.get_scheme(name).is_none() {
+                return Err(format!("Authentication scheme {} is not registered", name));
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn authorization_is_enabled(&self) -> bool {
-        self.global_authorization.is_some()
+        self.schemes.len() > 0
+    }
+
+    /// Schemes accepted by the action. No schemes specified by the action means - all of them
+    pub fn get_accepted_schemes(&self, names: Option<&[String]>) -> Vec<&ControllersAuthorization> {
+        match names {
+            Some(names) => self
+                .schemes
+                .iter()
+                .filter(|scheme| {
+                    names
+                        .iter()
+                        .any(|name| scheme.as_openid_str().eq_ignore_ascii_case(name))
+                })
+                .collect(),
+            None => self.schemes.iter().collect(),
+        }
+    }
+
+    /// Returns None if the action does not require authorization.
+    /// Otherwise - list of alternatives. Satisfying any of them is enough.
+    pub fn get_security_requirements<TGetShouldBeAuthorized: GetShouldBeAuthorized>(
+        &self,
+        action: &TGetShouldBeAuthorized,
+    ) -> Option<Vec<SecurityRequirement>> {
+        let accepted_schemes = self.get_accepted_schemes(action.get_authentication_schemes());
+
+        let result = match action.get_should_be_authorized() {
+            ShouldBeAuthorized::Yes => accepted_schemes
+                .into_iter()
+                .map(|scheme| SecurityRequirement {
+                    scheme,
+                    claims: RequiredClaims::no_claims(),
+                })
+                .collect(),
+            ShouldBeAuthorized::YesWithClaims(required_claims) => accepted_schemes
+                .into_iter()
+                .map(|scheme| SecurityRequirement {
+                    scheme,
+                    claims: required_claims.clone(),
+                })
+                .collect(),
+            ShouldBeAuthorized::No => return None,
+            ShouldBeAuthorized::UseGlobal => {
+                let result: Vec<_> = accepted_schemes
+                    .into_iter()
+                    .filter(|scheme| scheme.is_global_authorization_enabled())
+                    .map(|scheme| SecurityRequirement {
+                        scheme,
+                        claims: scheme.get_global_claims().clone(),
+                    })
+                    .collect();
+
+                if result.len() == 0 {
+                    return None;
+                }
+
+                result
+            }
+        };
+
+        Some(result)
     }
-    pub fn is_authorized<TGetShouldBeAuthorized: GetShouldBeAuthorized>(
+
+    pub async fn authorize<TGetShouldBeAuthorized: GetShouldBeAuthorized>(
+        &self,
+        action: &TGetShouldBeAuthorized,
+        request_credentials: &Option<Box<dyn RequestCredentials + Send + Sync + 'static>>,
+        ip: &str,
+        fail_context: &AuthorizationFailContext<'_>,
+        auth_error_factory: &Option<Arc<dyn AuthErrorFactory + Send + Sync + 'static>>,
+    ) -> Result<(), HttpFailResult> {
+        self.is_authorized(action, request_credentials, ip)
+            .await
+            .into_http_result(fail_context, auth_error_factory)
+    }
+
+    pub async fn is_authorized<TGetShouldBeAuthorized: GetShouldBeAuthorized>(
         &self,
         action: &TGetShouldBeAuthorized,
         request_credentials: &Option<Box<dyn RequestCredentials + Send + Sync + 'static>>,
         ip: &str,
     ) -> AuthorizationResult {
-        match action.get_should_be_authorized() {
-            ShouldBeAuthorized::Yes => {
-                if request_credentials.is_some() {
-                    return AuthorizationResult::Allowed;
-                } else {
-                    return AuthorizationResult::NotAuthenticated;
-                }
-            }
-            ShouldBeAuthorized::YesWithClaims(action_claims) => {
-                if let Some(req_credentials) = request_credentials {
-                    if let Some(claim_name) =
-                        action_claims.authorized_by_claims(ip, req_credentials.get_claims())
-                    {
-                        return AuthorizationResult::NotAuthorized(claim_name);
-                    } else {
-                        return AuthorizationResult::Allowed;
-                    }
-                } else {
-                    return AuthorizationResult::NotAuthenticated;
-                }
-            }
-            ShouldBeAuthorized::No => return AuthorizationResult::Allowed,
-            ShouldBeAuthorized::UseGlobal => {
-                if let Some(global_auth) = self.global_authorization.as_ref() {
-                    if global_auth.is_global_authorization_enabled() {
-                        if let Some(req_credentials) = request_credentials {
-                            let global_claims = global_auth.get_global_claims();
-
-                            if let Some(claim_name) =
-                                global_claims.authorized_by_claims(ip, req_credentials.get_claims())
-                            {
-                                return AuthorizationResult::NotAuthorized(claim_name);
-                            } else {
-                                return AuthorizationResult::Allowed;
-                            }
-                        } else {
-                            return AuthorizationResult::NotAuthenticated;
-                        }
-                    } else {
-                        return AuthorizationResult::Allowed;
-                    }
-                } else {
-                    return AuthorizationResult::Allowed;
+        let result = self
+            .is_authorized_by_claims(action, request_credentials, ip)
+            .await;
+
+        let required_permissions = action.get_required_permissions();
+
+        if !result.is_allowed() || required_permissions.len() == 0 {
+            return result;
+        }
+
+        let Some(req_credentials) = request_credentials else {
+            return AuthorizationResult::NotAuthenticated;
+        };
+
+        let request_claims = req_credentials.get_claims().unwrap_or_default();
+
+        match self.rbac.check_permissions(
+            &DateTimeAsMicroseconds::now(),
+            ip,
+            &request_claims,
+            required_permissions,
+        ) {
+            Ok(_) => AuthorizationResult::Allowed,
+            Err(reason) => AuthorizationResult::NotAuthorized(reason),
+        }
+    }
+
+    async fn is_authorized_by_claims<TGetShouldBeAuthorized: GetShouldBeAuthorized>(
+        &self,
+        action: &TGetShouldBeAuthorized,
+        request_credentials: &Option<Box<dyn RequestCredentials + Send + Sync + 'static>>,
+        ip: &str,
+    ) -> AuthorizationResult {
+        if let ShouldBeAuthorized::No = action.get_should_be_authorized() {
+            return AuthorizationResult::Allowed;
+        }
+
+        let Some(requirements) = self.get_security_requirements(action) else {
+            return AuthorizationResult::Allowed;
+        };
+
+        let Some(req_credentials) = request_credentials else {
+            return AuthorizationResult::NotAuthenticated;
+        };
+
+        // No schemes are registered - there is nothing to restrict. Only claims of the action are checked
+        if !self.authorization_is_enabled() {
+            return match action.get_should_be_authorized() {
+                ShouldBeAuthorized::YesWithClaims(action_claims) => {
+                    self.check_claims(req_credentials.as_ref(), action_claims, ip)
+                        .await
                 }
-            }
+                _ => AuthorizationResult::Allowed,
+            };
+        }
+
+        let Some(requirement) = self.get_caller_requirement(requirements, req_credentials.as_ref())
+        else {
+            return AuthorizationResult::NotAuthenticated;
+        };
+
+        self.check_claims(req_credentials.as_ref(), &requirement.claims, ip)
+            .await
+    }
+
+    /// Requirement of the scheme the caller is authenticated with.
+    /// None if the scheme can not be resolved or the action does not accept it
+    fn get_caller_requirement<'s>(
+        &self,
+        requirements: Vec<SecurityRequirement<'s>>,
+        request_credentials: &(dyn RequestCredentials + Send + Sync + 'static),
+    ) -> Option<SecurityRequirement<'s>> {
+        let scheme_name = match &self.scheme_resolver {
+            Some(scheme_resolver) => scheme_resolver.get_scheme_name(request_credentials)?,
+            None if self.schemes.len() == 1 => self.schemes[0].as_openid_str().to_string(),
+            None => return None,
+        };
+
+        requirements.into_iter().find(|requirement| {
+            requirement
+                .scheme
+                .as_openid_str()
+                .eq_ignore_ascii_case(&scheme_name)
+        })
+    }
+
+    async fn check_claims(
+        &self,
+        request_credentials: &(dyn RequestCredentials + Send + Sync + 'static),
+        required_claims: &RequiredClaims,
+        ip: &str,
+    ) -> AuthorizationResult {
+        // No Claims means - we are authorized
+        if !required_claims.has_claims() {
+            return AuthorizationResult::Allowed;
         }
+
+        self.provider
+            .authorize(request_credentials, required_claims, ip)
+            .await
     }
 }
 
@@ -109,7 +351,7 @@ impl AuthorizationMap {
 mod tests {
     use my_http_server::RequestClaim;
 
-    use crate::controllers::RequiredClaims;
+    use crate::controllers::{ApiKeyLocation, RequiredClaims, RoleDefinition};
 
     use super::*;
     use rust_extensions::date_time::*;
@@ -156,8 +398,8 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_global_auth_is_disabled_request_has_no_credentials() {
+    #[tokio::test]
+    async fn test_global_auth_is_disabled_request_has_no_credentials() {
         let auth_map = AuthorizationMap::new(None);
 
         let action: HttpActionMock = HttpActionMock {
@@ -166,13 +408,15 @@ mod tests {
 
         let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> = None;
 
-        let result = auth_map.is_authorized(&action, &client_credentials, "127.0.0.1");
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
 
         assert!(result.is_allowed());
     }
 
-    #[test]
-    fn test_global_auth_is_disabled_request_has_no_credentials_by_action_should_be_authorized_with_no_claims(
+    #[tokio::test]
+    async fn test_global_auth_is_disabled_request_has_no_credentials_by_action_should_be_authorized_with_no_claims(
     ) {
         let auth_map = AuthorizationMap::new(None);
 
@@ -182,13 +426,15 @@ mod tests {
 
         let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> = None;
 
-        let result = auth_map.is_authorized(&action, &client_credentials, "127.0.0.1");
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
 
         assert!(result.not_authenticated());
     }
 
-    #[test]
-    fn test_global_auth_is_disabled_request_credentials_with_no_claims() {
+    #[tokio::test]
+    async fn test_global_auth_is_disabled_request_credentials_with_no_claims() {
         let auth_map = AuthorizationMap::new(None);
 
         let action: HttpActionMock = HttpActionMock {
@@ -198,13 +444,15 @@ mod tests {
         let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> =
             Some(Box::new(RequestCredentialsMock { value: None }));
 
-        let result = auth_map.is_authorized(&action, &client_credentials, "127.0.0.1");
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
 
         assert!(result.is_allowed());
     }
 
-    #[test]
-    fn test_global_auth_is_disabled_by_setup_with_no_claims_request_has_no_credentials() {
+    #[tokio::test]
+    async fn test_global_auth_is_disabled_by_setup_with_no_claims_request_has_no_credentials() {
         let auth_map = AuthorizationMap::new(
             ControllersAuthorization::BearerAuthentication {
                 global: false,
@@ -219,13 +467,15 @@ mod tests {
 
         let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> = None;
 
-        let result = auth_map.is_authorized(&action, &client_credentials, "127.0.0.1");
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
 
         assert!(result.is_allowed());
     }
 
-    #[test]
-    fn test_global_enabled_with_no_global_claims_and_not_request_credentials_action_is_setup_to_global_scheme(
+    #[tokio::test]
+    async fn test_global_enabled_with_no_global_claims_and_not_request_credentials_action_is_setup_to_global_scheme(
     ) {
         let auth_map = AuthorizationMap::new(
             ControllersAuthorization::BearerAuthentication {
@@ -241,13 +491,15 @@ mod tests {
 
         let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> = None;
 
-        let result = auth_map.is_authorized(&action, &client_credentials, "127.0.0.1");
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
 
         assert!(result.not_authenticated());
     }
 
-    #[test]
-    fn test_global_enabled_with_no_global_claims_action_is_setup_to_global_scheme_request_has_creds_with_no_claims(
+    #[tokio::test]
+    async fn test_global_enabled_with_no_global_claims_action_is_setup_to_global_scheme_request_has_creds_with_no_claims(
     ) {
         let auth_map = AuthorizationMap::new(
             ControllersAuthorization::BearerAuthentication {
@@ -263,13 +515,15 @@ mod tests {
 
         let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> = None;
 
-        let result = auth_map.is_authorized(&action, &client_credentials, "127.0.0.1");
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
 
         assert!(result.not_authenticated());
     }
 
-    #[test]
-    fn test_global_disabled_action_should_be_authorized_with_no_claims() {
+    #[tokio::test]
+    async fn test_global_disabled_action_should_be_authorized_with_no_claims() {
         let auth_map = AuthorizationMap::new(None);
 
         let action: HttpActionMock = HttpActionMock {
@@ -278,13 +532,15 @@ mod tests {
 
         let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> = None;
 
-        let result = auth_map.is_authorized(&action, &client_credentials, "127.0.0.1");
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
 
         assert!(result.not_authenticated());
     }
 
-    #[test]
-    fn test_global_disabled_action_should_be_authorized_with_test_claim() {
+    #[tokio::test]
+    async fn test_global_disabled_action_should_be_authorized_with_test_claim() {
         let auth_map = AuthorizationMap::new(None);
 
         let action: HttpActionMock = HttpActionMock {
@@ -296,8 +552,441 @@ mod tests {
         let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> =
             Some(Box::new(RequestCredentialsMock { value: None }));
 
-        let result = auth_map.is_authorized(&action, &client_credentials, "127.0.0.1");
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
+
+        assert!(result.not_authorized());
+    }
+
+    #[test]
+    fn test_security_requirements_resolve_global_claims_per_scheme() {
+        let auth_map = AuthorizationMap::from_schemes(vec![
+            ControllersAuthorization::BearerAuthentication {
+                global: true,
+                global_claims: RequiredClaims::from_slice_of_str(&["admin"]),
+            },
+            ControllersAuthorization::api_keys(true, RequiredClaims::no_claims()),
+            ControllersAuthorization::BasicAuthentication {
+                global: false,
+                global_claims: RequiredClaims::no_claims(),
+            },
+        ])
+        .unwrap();
+
+        let action = HttpActionMock {
+            value: ShouldBeAuthorized::UseGlobal,
+        };
+
+        let result = auth_map.get_security_requirements(&action).unwrap();
+
+        assert_eq!(2, result.len());
+        assert_eq!("BearerAuth", result[0].scheme.as_openid_str());
+        assert_eq!("admin", result[0].claims.get_expression().to_string());
+        assert_eq!("ApiKeyAuth", result[1].scheme.as_openid_str());
+        assert!(!result[1].claims.has_claims());
+    }
+
+    #[test]
+    fn test_security_requirements_global_is_disabled() {
+        let auth_map = AuthorizationMap::new(
+            ControllersAuthorization::BearerAuthentication {
+                global: false,
+                global_claims: RequiredClaims::from_slice_of_str(&["admin"]),
+            }
+            .into(),
+        );
+
+        let action = HttpActionMock {
+            value: ShouldBeAuthorized::UseGlobal,
+        };
+        assert!(auth_map.get_security_requirements(&action).is_none());
+
+        let action = HttpActionMock {
+            value: ShouldBeAuthorized::Yes,
+        };
+        assert_eq!(
+            1,
+            auth_map.get_security_requirements(&action).unwrap().len()
+        );
+    }
+
+    pub struct SchemeActionMock {
+        value: ShouldBeAuthorized,
+        schemes: Option<Vec<String>>,
+    }
+
+    impl GetShouldBeAuthorized for SchemeActionMock {
+        fn get_should_be_authorized(&self) -> &ShouldBeAuthorized {
+            &self.value
+        }
+
+        fn get_authentication_schemes(&self) -> Option<&[String]> {
+            self.schemes.as_deref()
+        }
+    }
+
+    /// Id of the credentials is the name of the scheme they are authenticated with
+    pub struct SchemeCredentialsMock {
+        scheme: &'static str,
+        claims: Vec<ClaimMock>,
+    }
+
+    impl RequestCredentials for SchemeCredentialsMock {
+        fn get_id(&self) -> &str {
+            self.scheme
+        }
+
+        fn get_claims(&self) -> Option<Vec<RequestClaim>> {
+            let result = self
+                .claims
+                .iter()
+                .map(|claim| RequestClaim {
+                    id: &claim.id,
+                    expires: claim.expires,
+                    allowed_ips: claim.allowed_ips.as_ref(),
+                })
+                .collect();
+
+            Some(result)
+        }
+    }
+
+    pub struct SchemeResolverMock;
+
+    impl AuthenticationSchemeResolver for SchemeResolverMock {
+        fn get_scheme_name(
+            &self,
+            request_credentials: &(dyn RequestCredentials + Send + Sync + 'static),
+        ) -> Option<String> {
+            Some(request_credentials.get_id().to_string())
+        }
+    }
+
+    fn create_claim(id: &str) -> ClaimMock {
+        ClaimMock {
+            id: id.to_string(),
+            expires: DateTimeAsMicroseconds::new(
+                DateTimeAsMicroseconds::now().unix_microseconds + 60_000_000,
+            ),
+            allowed_ips: None,
+        }
+    }
+
+    fn create_bearer_and_api_key_map() -> AuthorizationMap {
+        let mut auth_map = AuthorizationMap::from_schemes(vec![
+            ControllersAuthorization::BearerAuthentication {
+                global: true,
+                global_claims: RequiredClaims::from_slice_of_str(&["admin"]),
+            },
+            ControllersAuthorization::api_keys(
+                true,
+                RequiredClaims::from_slice_of_str(&["machine"]),
+            ),
+        ])
+        .unwrap();
+
+        auth_map.set_scheme_resolver(Arc::new(SchemeResolverMock));
+        auth_map
+    }
+
+    #[tokio::test]
+    async fn test_global_claims_of_caller_scheme_are_checked() {
+        let auth_map = create_bearer_and_api_key_map();
+
+        let action = HttpActionMock {
+            value: ShouldBeAuthorized::UseGlobal,
+        };
+
+        let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> =
+            Some(Box::new(SchemeCredentialsMock {
+                scheme: "ApiKeyAuth",
+                claims: vec![create_claim("machine")],
+            }));
+
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
+        assert!(result.is_allowed());
+
+        // Claim of ApiKey scheme does not satisfy Bearer scheme
+        let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> =
+            Some(Box::new(SchemeCredentialsMock {
+                scheme: "BearerAuth",
+                claims: vec![create_claim("machine")],
+            }));
+
+        match auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await
+        {
+            AuthorizationResult::NotAuthorized(reason) => assert_eq!(
+                AuthorizationDenialReason::ClaimIsMissing("admin".to_string()),
+                reason
+            ),
+            _ => panic!("Request should not be authorized"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_key_caller_is_rejected_by_bearer_only_action() {
+        let auth_map = create_bearer_and_api_key_map();
+
+        let api_key_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> =
+            Some(Box::new(SchemeCredentialsMock {
+                scheme: "ApiKeyAuth",
+                claims: vec![create_claim("machine"), create_claim("admin")],
+            }));
+
+        let bearer_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> =
+            Some(Box::new(SchemeCredentialsMock {
+                scheme: "BearerAuth",
+                claims: vec![create_claim("admin")],
+            }));
+
+        for value in [
+            ShouldBeAuthorized::Yes,
+            ShouldBeAuthorized::YesWithClaims(RequiredClaims::from_slice_of_str(&["admin"])),
+            ShouldBeAuthorized::UseGlobal,
+        ] {
+            let action = SchemeActionMock {
+                value,
+                schemes: Some(vec!["BearerAuth".to_string()]),
+            };
+
+            let result = auth_map
+                .is_authorized(&action, &api_key_credentials, "127.0.0.1")
+                .await;
+            assert!(result.not_authenticated());
+
+            let result = auth_map
+                .is_authorized(&action, &bearer_credentials, "127.0.0.1")
+                .await;
+            assert!(result.is_allowed());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_caller_scheme_can_not_be_resolved() {
+        let mut auth_map = create_bearer_and_api_key_map();
+        auth_map.scheme_resolver = None;
+
+        let action = HttpActionMock {
+            value: ShouldBeAuthorized::Yes,
+        };
+
+        let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> =
+            Some(Box::new(SchemeCredentialsMock {
+                scheme: "BearerAuth",
+                claims: vec![create_claim("admin")],
+            }));
+
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
+        assert!(result.not_authenticated());
+    }
+
+    #[test]
+    fn test_api_keys_from_several_locations() {
+        let auth_map = AuthorizationMap::from_schemes(vec![
+            ControllersAuthorization::ApiKeys {
+                global: true,
+                global_claims: RequiredClaims::no_claims(),
+                location: ApiKeyLocation::header("Authorization").with_prefix("ApiKey"),
+            },
+            ControllersAuthorization::ApiKeys {
+                global: true,
+                global_claims: RequiredClaims::no_claims(),
+                location: ApiKeyLocation::query("api_key").with_scheme_name("ApiKeyQueryAuth"),
+            },
+        ])
+        .unwrap();
+
+        let action = HttpActionMock {
+            value: ShouldBeAuthorized::UseGlobal,
+        };
+
+        let result = auth_map.get_security_requirements(&action).unwrap();
+
+        assert_eq!(2, result.len());
+        assert_eq!("ApiKeyAuth", result[0].scheme.as_openid_str());
+        assert_eq!("ApiKeyQueryAuth", result[1].scheme.as_openid_str());
+    }
+
+    #[test]
+    fn test_duplicated_scheme_is_rejected() {
+        let result = AuthorizationMap::from_schemes(vec![
+            ControllersAuthorization::api_keys(true, RequiredClaims::no_claims()),
+            ControllersAuthorization::api_keys(false, RequiredClaims::no_claims()),
+        ]);
+
+        assert!(result.is_err());
+    }
+
+    pub struct AuthorizationProviderMock {
+        pub calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl AuthorizationProvider for AuthorizationProviderMock {
+        async fn authorize(
+            &self,
+            request_credentials: &(dyn RequestCredentials + Send + Sync + 'static),
+            required_claims: &RequiredClaims,
+            _ip: &str,
+        ) -> AuthorizationResult {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+            if request_credentials.get_id() == "test"
+                && required_claims.get_expression().to_string() == "accounts"
+            {
+                AuthorizationResult::Allowed
+            } else {
+                AuthorizationResult::NotAuthorized(AuthorizationDenialReason::PolicyDenied(
+                    required_claims.get_expression().to_string(),
+                ))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authorization_is_delegated_to_provider_and_cached() {
+        let provider = Arc::new(AuthorizationProviderMock {
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        });
+
+        let mut auth_map = AuthorizationMap::new(None);
+        auth_map.set_provider(provider.clone(), Some(Duration::from_secs(60)));
+
+        let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> =
+            Some(Box::new(RequestCredentialsMock { value: None }));
+
+        let action = HttpActionMock {
+            value: ShouldBeAuthorized::YesWithClaims(RequiredClaims::parse("accounts").unwrap()),
+        };
+
+        for _ in 0..3 {
+            let result = auth_map
+                .is_authorized(&action, &client_credentials, "127.0.0.1")
+                .await;
+            assert!(result.is_allowed());
+        }
+
+        assert_eq!(1, provider.calls.load(std::sync::atomic::Ordering::SeqCst));
+
+        let action = HttpActionMock {
+            value: ShouldBeAuthorized::YesWithClaims(RequiredClaims::parse("admin").unwrap()),
+        };
+
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
 
         assert!(result.not_authorized());
+        assert_eq!(2, provider.calls.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_provider_is_not_called_without_required_claims() {
+        let provider = Arc::new(AuthorizationProviderMock {
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        });
+
+        let mut auth_map = AuthorizationMap::new(None);
+        auth_map.set_provider(provider.clone(), None);
+
+        let client_credentials: Option<Box<dyn RequestCredentials + Send + Sync + 'static>> =
+            Some(Box::new(RequestCredentialsMock { value: None }));
+
+        let action = HttpActionMock {
+            value: ShouldBeAuthorized::YesWithClaims(RequiredClaims::no_claims()),
+        };
+
+        let result = auth_map
+            .is_authorized(&action, &client_credentials, "127.0.0.1")
+            .await;
+
+        assert!(result.is_allowed());
+        assert_eq!(0, provider.calls.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    pub struct PermissionsActionMock {
+        value: ShouldBeAuthorized,
+        permissions: Vec<String>,
+    }
+
+    impl GetShouldBeAuthorized for PermissionsActionMock {
+        fn get_should_be_authorized(&self) -> &ShouldBeAuthorized {
+            &self.value
+        }
+
+        fn get_required_permissions(&self) -> &[String] {
+            &self.permissions
+        }
+    }
+
+    fn create_rbac_map() -> AuthorizationMap {
+        let mut auth_map = AuthorizationMap::new(None);
+
+        auth_map
+            .rbac
+            .add_role(RoleDefinition::new("viewer").with_permissions(&["accounts.read"]))
+            .unwrap();
+
+        auth_map
+            .rbac
+            .add_role(
+                RoleDefinition::new("admin")
+                    .with_permissions(&["accounts.write"])
+                    .with_included_roles(&["viewer"]),
+            )
+            .unwrap();
+
+        auth_map
+    }
+
+    fn create_credentials(
+        claims: &[&str],
+    ) -> Option<Box<dyn RequestCredentials + Send + Sync + 'static>> {
+        Some(Box::new(RequestCredentialsMock {
+            value: Some(claims.iter().map(|claim| create_claim(claim)).collect()),
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_required_permissions() {
+        let auth_map = create_rbac_map();
+
+        let action = PermissionsActionMock {
+            value: ShouldBeAuthorized::Yes,
+            permissions: vec!["accounts.read".to_string()],
+        };
+
+        // Permission of the included role
+        let result = auth_map
+            .is_authorized(&action, &create_credentials(&["admin"]), "127.0.0.1")
+            .await;
+        assert!(result.is_allowed());
+
+        let action = PermissionsActionMock {
+            value: ShouldBeAuthorized::Yes,
+            permissions: vec!["accounts.read".to_string(), "accounts.write".to_string()],
+        };
+
+        let result = auth_map
+            .is_authorized(&action, &create_credentials(&["viewer"]), "127.0.0.1")
+            .await;
+
+        match result {
+            AuthorizationResult::NotAuthorized(reason) => assert_eq!(
+                reason,
+                AuthorizationDenialReason::PermissionIsMissing("accounts.write".to_string())
+            ),
+            _ => panic!("Missing permission is expected"),
+        }
+
+        let result = auth_map.is_authorized(&action, &None, "127.0.0.1").await;
+        assert!(matches!(result, AuthorizationResult::NotAuthenticated));
     }
 }
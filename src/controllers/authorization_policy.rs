@@ -0,0 +1,16 @@
+use my_http_server::RequestCredentials;
+
+use super::{AuthorizationResult, HttpRoute, HttpRouteValues};
+
+/// Resource based authorization. Executed after claims are checked, so the policy can rely on route values
+/// and credentials to decide whether the caller may access the resource.
+/// Deny with `AuthorizationDenialReason::PolicyDenied` to let AuthErrorFactory tell it apart from claims checks.
+#[async_trait::async_trait]
+pub trait AuthorizationPolicy {
+    async fn authorize(
+        &self,
+        http_route: &HttpRoute,
+        route_values: &HttpRouteValues<'_>,
+        request_credentials: &Option<Box<dyn RequestCredentials + Send + Sync + 'static>>,
+    ) -> AuthorizationResult;
+}
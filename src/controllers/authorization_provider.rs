@@ -0,0 +1,341 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use my_http_server::RequestCredentials;
+
+use super::{AuthorizationResult, RequiredClaims};
+
+/// Decides whether the credentials satisfy the required claims.
+/// Called only if credentials are present and the action requires claims.
+#[async_trait::async_trait]
+pub trait AuthorizationProvider {
+    async fn authorize(
+        &self,
+        request_credentials: &(dyn RequestCredentials + Send + Sync + 'static),
+        required_claims: &RequiredClaims,
+        ip: &str,
+    ) -> AuthorizationResult;
+}
+
+/// Default provider. Checks claims attached to the request credentials
+pub struct ClaimsAuthorizationProvider;
+
+#[async_trait::async_trait]
+impl AuthorizationProvider for ClaimsAuthorizationProvider {
+    async fn authorize(
+        &self,
+        request_credentials: &(dyn RequestCredentials + Send + Sync + 'static),
+        required_claims: &RequiredClaims,
+        ip: &str,
+    ) -> AuthorizationResult {
+        match required_claims.authorized_by_claims(ip, request_credentials.get_claims()) {
+            Ok(_) => AuthorizationResult::Allowed,
+            Err(err) => err.into(),
+        }
+    }
+}
+
+const DEFAULT_CACHE_CAPACITY: usize = 10_000;
+
+/// Caches results of the inner provider by credentials id, required claims and ip.
+/// Keep ttl short: cached Allowed result does not notice that claims are expired or revoked.
+/// Denials are not cached unless negative ttl is set. If capacity is reached - the oldest results are evicted.
+pub struct CachedAuthorizationProvider {
+    inner: Arc<dyn AuthorizationProvider + Send + Sync + 'static>,
+    ttl: Duration,
+    negative_ttl: Option<Duration>,
+    capacity: usize,
+    cache: Mutex<CachedResults>,
+}
+
+impl CachedAuthorizationProvider {
+    pub fn new(
+        inner: Arc<dyn AuthorizationProvider + Send + Sync + 'static>,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            inner,
+            ttl,
+            negative_ttl: None,
+            capacity: DEFAULT_CACHE_CAPACITY,
+            cache: Mutex::new(CachedResults::new()),
+        }
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Caches denials as well. Keep it shorter than ttl: denied caller which got the claim is denied until it expires
+    pub fn with_negative_ttl(mut self, negative_ttl: Duration) -> Self {
+        self.negative_ttl = Some(negative_ttl);
+        self
+    }
+
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    fn get_from_cache(&self, key: &str) -> Option<AuthorizationResult> {
+        let mut cache = self.cache.lock().unwrap();
+        let (result, expires) = cache.items.get(key)?;
+
+        if *expires < Instant::now() {
+            cache.items.remove(key);
+            return None;
+        }
+
+        Some(result.clone())
+    }
+
+    fn save_to_cache(&self, key: String, result: AuthorizationResult) {
+        let ttl = if result.is_allowed() {
+            self.ttl
+        } else {
+            match self.negative_ttl {
+                Some(negative_ttl) => negative_ttl,
+                None => return,
+            }
+        };
+
+        if self.capacity == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut cache = self.cache.lock().unwrap();
+
+        cache.remove_expired(now);
+
+        while cache.items.len() >= self.capacity {
+            if !cache.remove_oldest() {
+                break;
+            }
+        }
+
+        cache.insert(key, result, now + ttl);
+    }
+}
+
+/// Results and the order they were saved in. Only the head of the queue is checked for expiration,
+/// so saving the result costs O(1) amortized. Expired results behind the head are removed on read.
+struct CachedResults {
+    items: HashMap<String, (AuthorizationResult, Instant)>,
+    queue: VecDeque<(String, Instant)>,
+}
+
+impl CachedResults {
+    fn new() -> Self {
+        Self {
+            items: HashMap::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.items.clear();
+        self.queue.clear();
+    }
+
+    fn insert(&mut self, key: String, result: AuthorizationResult, expires: Instant) {
+        self.queue.push_back((key.clone(), expires));
+        self.items.insert(key, (result, expires));
+    }
+
+    fn remove_expired(&mut self, now: Instant) {
+        while let Some((_, expires)) = self.queue.front() {
+            if *expires >= now {
+                break;
+            }
+
+            self.remove_oldest();
+        }
+    }
+
+    fn remove_oldest(&mut self) -> bool {
+        let Some((key, expires)) = self.queue.pop_front() else {
+            return false;
+        };
+
+        // Key could be saved again after the queued result had been removed on read
+        if let Some((_, item_expires)) = self.items.get(&key) {
+            if *item_expires == expires {
+                self.items.remove(&key);
+            }
+        }
+
+        true
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthorizationProvider for CachedAuthorizationProvider {
+    async fn authorize(
+        &self,
+        request_credentials: &(dyn RequestCredentials + Send + Sync + 'static),
+        required_claims: &RequiredClaims,
+        ip: &str,
+    ) -> AuthorizationResult {
+        let key = format!(
+            "{}|{}|{}",
+            request_credentials.get_id(),
+            required_claims.get_expression(),
+            ip
+        );
+
+        if let Some(result) = self.get_from_cache(&key) {
+            return result;
+        }
+
+        let result = self
+            .inner
+            .authorize(request_credentials, required_claims, ip)
+            .await;
+
+        self.save_to_cache(key, result.clone());
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use my_http_server::RequestClaim;
+
+    use super::*;
+    use crate::controllers::AuthorizationDenialReason;
+
+    struct CredentialsMock {
+        id: String,
+    }
+
+    impl RequestCredentials for CredentialsMock {
+        fn get_id(&self) -> &str {
+            &self.id
+        }
+
+        fn get_claims(&self) -> Option<Vec<RequestClaim>> {
+            None
+        }
+    }
+
+    struct ProviderMock {
+        allowed: bool,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl AuthorizationProvider for ProviderMock {
+        async fn authorize(
+            &self,
+            _request_credentials: &(dyn RequestCredentials + Send + Sync + 'static),
+            required_claims: &RequiredClaims,
+            _ip: &str,
+        ) -> AuthorizationResult {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+
+            if self.allowed {
+                AuthorizationResult::Allowed
+            } else {
+                AuthorizationResult::NotAuthorized(AuthorizationDenialReason::ForbiddenClaims(
+                    required_claims.get_expression().to_string(),
+                ))
+            }
+        }
+    }
+
+    fn create_provider(allowed: bool) -> Arc<ProviderMock> {
+        Arc::new(ProviderMock {
+            allowed,
+            calls: AtomicUsize::new(0),
+        })
+    }
+
+    fn get_cached_amount(provider: &CachedAuthorizationProvider) -> usize {
+        provider.cache.lock().unwrap().items.len()
+    }
+
+    async fn authorize(provider: &CachedAuthorizationProvider, id: &str) -> AuthorizationResult {
+        let credentials = CredentialsMock { id: id.to_string() };
+        let required_claims = RequiredClaims::parse("admin").unwrap();
+
+        provider
+            .authorize(&credentials, &required_claims, "127.0.0.1")
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_allowed_result_is_cached() {
+        let inner = create_provider(true);
+        let provider = CachedAuthorizationProvider::new(inner.clone(), Duration::from_secs(60));
+
+        assert!(authorize(&provider, "user").await.is_allowed());
+        assert!(authorize(&provider, "user").await.is_allowed());
+
+        assert_eq!(1, inner.calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_denial_is_cached_only_with_negative_ttl() {
+        let inner = create_provider(false);
+        let provider = CachedAuthorizationProvider::new(inner.clone(), Duration::from_secs(60));
+
+        assert!(!authorize(&provider, "user").await.is_allowed());
+        assert!(!authorize(&provider, "user").await.is_allowed());
+
+        assert_eq!(2, inner.calls.load(Ordering::SeqCst));
+        assert_eq!(0, get_cached_amount(&provider));
+
+        let inner = create_provider(false);
+        let provider = CachedAuthorizationProvider::new(inner.clone(), Duration::from_secs(60))
+            .with_negative_ttl(Duration::from_secs(5));
+
+        assert!(!authorize(&provider, "user").await.is_allowed());
+        assert!(!authorize(&provider, "user").await.is_allowed());
+
+        assert_eq!(1, inner.calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_oldest_result_is_evicted_at_capacity() {
+        let inner = create_provider(true);
+        let provider = CachedAuthorizationProvider::new(inner.clone(), Duration::from_secs(60))
+            .with_capacity(2);
+
+        authorize(&provider, "first").await;
+        authorize(&provider, "second").await;
+        authorize(&provider, "third").await;
+
+        assert_eq!(2, get_cached_amount(&provider));
+
+        authorize(&provider, "third").await;
+        authorize(&provider, "second").await;
+        assert_eq!(3, inner.calls.load(Ordering::SeqCst));
+
+        authorize(&provider, "first").await;
+        assert_eq!(4, inner.calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_expired_result_is_evicted() {
+        let inner = create_provider(true);
+        let provider = CachedAuthorizationProvider::new(inner.clone(), Duration::from_millis(1));
+
+        authorize(&provider, "first").await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        authorize(&provider, "second").await;
+
+        assert_eq!(1, get_cached_amount(&provider));
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        authorize(&provider, "second").await;
+
+        assert_eq!(3, inner.calls.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,489 @@
+use std::fmt::Display;
+
+use my_http_server::RequestClaim;
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use super::AuthorizationDenialReason;
+
+/// Boolean expression over claims.
+/// String form: `admin | (support & read-only)`, `!guest`. `&` binds tighter than `|`.
+#[derive(Debug, Clone)]
+pub enum ClaimsExpression {
+    Claim(String),
+    AllOf(Vec<ClaimsExpression>),
+    AnyOf(Vec<ClaimsExpression>),
+    Not(Box<ClaimsExpression>),
+}
+
+impl ClaimsExpression {
+    pub fn claim(name: &str) -> Self {
+        Self::Claim(name.to_string())
+    }
+
+    pub fn all_of(items: Vec<ClaimsExpression>) -> Self {
+        Self::AllOf(items)
+    }
+
+    /// Empty any of can not be satisfied by any claims
+    pub fn any_of(items: Vec<ClaimsExpression>) -> Result<Self, String> {
+        if items.len() == 0 {
+            return Err("Any of claims expression can not be empty".to_string());
+        }
+
+        Ok(Self::AnyOf(items))
+    }
+
+    pub fn not(item: ClaimsExpression) -> Self {
+        Self::Not(Box::new(item))
+    }
+
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let mut parser = ClaimsExpressionParser {
+            src: src.as_bytes(),
+            pos: 0,
+        };
+
+        let result = parser.parse_any_of()?;
+
+        parser.skip_spaces();
+        if parser.pos < parser.src.len() {
+            return Err(format!(
+                "Invalid claims expression '{}'. Unexpected symbol at position {}",
+                src, parser.pos
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Alternative sets of claims the expression requires to be present: `admin | (support & read)` gives
+    /// `[[admin], [support, read]]`. Any of the sets is enough. Negated claims are never listed
+    pub fn get_required_claim_sets(&self) -> Vec<Vec<&str>> {
+        match self {
+            ClaimsExpression::Claim(name) => vec![vec![name.as_str()]],
+            ClaimsExpression::AllOf(items) => {
+                let mut result = vec![vec![]];
+
+                for itm in items {
+                    let mut combined = Vec::new();
+
+                    for claim_set in &result {
+                        for itm_claim_set in itm.get_required_claim_sets() {
+                            let mut claim_set = claim_set.clone();
+
+                            for claim in itm_claim_set {
+                                if !claim_set.contains(&claim) {
+                                    claim_set.push(claim);
+                                }
+                            }
+
+                            push_claim_set(&mut combined, claim_set);
+                        }
+                    }
+
+                    result = combined;
+                }
+
+                result
+            }
+            ClaimsExpression::AnyOf(items) => {
+                let mut result = Vec::new();
+
+                for itm in items {
+                    for claim_set in itm.get_required_claim_sets() {
+                        push_claim_set(&mut result, claim_set);
+                    }
+                }
+
+                result
+            }
+            ClaimsExpression::Not(_) => vec![vec![]],
+        }
+    }
+
+    /// True if the expression is satisfied without any claims. Empty any of is never satisfied, so it is not empty
+    pub fn is_empty(&self) -> bool {
+        match self {
+            ClaimsExpression::Claim(_) => false,
+            ClaimsExpression::AllOf(items) => items.iter().all(|itm| itm.is_empty()),
+            ClaimsExpression::AnyOf(_) => false,
+            ClaimsExpression::Not(_) => false,
+        }
+    }
+
+    /// Checks claims against the expression. Error holds the first failing branch
+    pub fn check(
+        &self,
+        now: &DateTimeAsMicroseconds,
+        request_ip: &str,
+        request_claims: &[RequestClaim],
+    ) -> Result<(), AuthorizationDenialReason> {
+        match self {
+            ClaimsExpression::Claim(name) => {
+                let Some(request_claim) = request_claims.iter().find(|c| c.id == name) else {
+                    return Err(AuthorizationDenialReason::ClaimIsMissing(name.to_string()));
+                };
+
+                if !request_claim.is_ip_allowed(request_ip) {
+                    return Err(AuthorizationDenialReason::IpIsNotAllowed {
+                        claim: name.to_string(),
+                        ip: request_ip.to_string(),
+                    });
+                }
+
+                if request_claim.expires.unix_microseconds < now.unix_microseconds {
+                    return Err(AuthorizationDenialReason::ClaimIsExpired(name.to_string()));
+                }
+
+                Ok(())
+            }
+            ClaimsExpression::AllOf(items) => {
+                for itm in items {
+                    itm.check(now, request_ip, request_claims)?;
+                }
+
+                Ok(())
+            }
+            ClaimsExpression::AnyOf(items) => {
+                let mut first_failed = None;
+
+                for itm in items {
+                    match itm.check(now, request_ip, request_claims) {
+                        Ok(_) => return Ok(()),
+                        Err(err) => {
+                            if first_failed.is_none() {
+                                first_failed = Some(err);
+                            }
+                        }
+                    }
+                }
+
+                match first_failed {
+                    Some(err) => Err(err),
+                    None => Err(AuthorizationDenialReason::ForbiddenClaims(
+                        "empty any of expression".to_string(),
+                    )),
+                }
+            }
+            ClaimsExpression::Not(item) => match item.check(now, request_ip, request_claims) {
+                Ok(_) => Err(AuthorizationDenialReason::ForbiddenClaims(self.to_string())),
+                Err(_) => Ok(()),
+            },
+        }
+    }
+
+    fn write_as_operand(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClaimsExpression::Claim(_) | ClaimsExpression::Not(_) => write!(f, "{}", self),
+            _ => write!(f, "({})", self),
+        }
+    }
+}
+
+impl Display for ClaimsExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClaimsExpression::Claim(name) => write!(f, "{}", name),
+            ClaimsExpression::AllOf(items) => write_items(f, items, " & "),
+            ClaimsExpression::AnyOf(items) => write_items(f, items, " | "),
+            ClaimsExpression::Not(item) => {
+                write!(f, "!")?;
+                item.write_as_operand(f)
+            }
+        }
+    }
+}
+
+fn push_claim_set<'s>(result: &mut Vec<Vec<&'s str>>, claim_set: Vec<&'s str>) {
+    let exists = result.iter().any(|itm| {
+        itm.len() == claim_set.len() && itm.iter().all(|claim| claim_set.contains(claim))
+    });
+
+    if !exists {
+        result.push(claim_set);
+    }
+}
+
+fn write_items(
+    f: &mut std::fmt::Formatter<'_>,
+    items: &[ClaimsExpression],
+    separator: &str,
+) -> std::fmt::Result {
+    for (index, itm) in items.iter().enumerate() {
+        if index > 0 {
+            write!(f, "{}", separator)?;
+        }
+
+        itm.write_as_operand(f)?;
+    }
+
+    Ok(())
+}
+
+struct ClaimsExpressionParser<'s> {
+    src: &'s [u8],
+    pos: usize,
+}
+
+impl<'s> ClaimsExpressionParser<'s> {
+    fn skip_spaces(&mut self) {
+        while self.pos < self.src.len() && self.src[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn try_consume(&mut self, operator: u8) -> bool {
+        self.skip_spaces();
+
+        if self.pos < self.src.len() && self.src[self.pos] == operator {
+            self.pos += 1;
+            // `&&` and `||` are accepted as well
+            if self.pos < self.src.len() && self.src[self.pos] == operator {
+                self.pos += 1;
+            }
+            return true;
+        }
+
+        false
+    }
+
+    fn parse_any_of(&mut self) -> Result<ClaimsExpression, String> {
+        let mut items = vec![self.parse_all_of()?];
+
+        while self.try_consume(b'|') {
+            items.push(self.parse_all_of()?);
+        }
+
+        if items.len() == 1 {
+            return Ok(items.remove(0));
+        }
+
+        Ok(ClaimsExpression::AnyOf(items))
+    }
+
+    fn parse_all_of(&mut self) -> Result<ClaimsExpression, String> {
+        let mut items = vec![self.parse_operand()?];
+
+        while self.try_consume(b'&') {
+            items.push(self.parse_operand()?);
+        }
+
+        if items.len() == 1 {
+            return Ok(items.remove(0));
+        }
+
+        Ok(ClaimsExpression::AllOf(items))
+    }
+
+    fn parse_operand(&mut self) -> Result<ClaimsExpression, String> {
+        if self.try_consume(b'!') {
+            return Ok(ClaimsExpression::not(self.parse_operand()?));
+        }
+
+        if self.try_consume(b'(') {
+            let result = self.parse_any_of()?;
+
+            if !self.try_consume(b')') {
+                return Err(format!(
+                    "Closing bracket is expected at position {}",
+                    self.pos
+                ));
+            }
+
+            return Ok(result);
+        }
+
+        let start = self.pos;
+
+        while self.pos < self.src.len() && is_claim_symbol(self.src[self.pos]) {
+            self.pos += 1;
+        }
+
+        if start == self.pos {
+            return Err(format!("Claim name is expected at position {}", self.pos));
+        }
+
+        let name = std::str::from_utf8(&self.src[start..self.pos]).unwrap();
+        Ok(ClaimsExpression::claim(name))
+    }
+}
+
+fn is_claim_symbol(b: u8) -> bool {
+    !b.is_ascii_whitespace() && b != b'&' && b != b'|' && b != b'!' && b != b'(' && b != b')'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_future() -> DateTimeAsMicroseconds {
+        DateTimeAsMicroseconds::new(DateTimeAsMicroseconds::now().unix_microseconds + 60_000_000)
+    }
+
+    fn create_claims<'s>(ids: &[&'s str]) -> Vec<RequestClaim<'s>> {
+        ids.iter()
+            .map(|id| RequestClaim {
+                id,
+                expires: in_future(),
+                allowed_ips: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let expression = ClaimsExpression::parse("admin | (support & read-only)").unwrap();
+        assert_eq!("admin | (support & read-only)", expression.to_string());
+
+        let expression = ClaimsExpression::parse("!guest && (a || b)").unwrap();
+        assert_eq!("!guest & (a | b)", expression.to_string());
+    }
+
+    #[test]
+    fn test_required_claim_sets() {
+        let expression =
+            ClaimsExpression::parse("admin | (support & read) & !guest | admin").unwrap();
+        assert_eq!(
+            expression.get_required_claim_sets(),
+            vec![vec!["admin"], vec!["support", "read"]]
+        );
+
+        let expression = ClaimsExpression::parse("(admin | support) & read").unwrap();
+        assert_eq!(
+            expression.get_required_claim_sets(),
+            vec![vec!["admin", "read"], vec!["support", "read"]]
+        );
+
+        let expression = ClaimsExpression::parse("!guest").unwrap();
+        assert_eq!(
+            expression.get_required_claim_sets(),
+            vec![Vec::<&str>::new()]
+        );
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(ClaimsExpression::parse("").is_err());
+        assert!(ClaimsExpression::parse("admin |").is_err());
+        assert!(ClaimsExpression::parse("(admin").is_err());
+        assert!(ClaimsExpression::parse("admin)").is_err());
+    }
+
+    #[test]
+    fn test_any_of_all_of() {
+        let expression = ClaimsExpression::parse("admin | (support & read-only)").unwrap();
+
+        assert!(expression
+            .check(
+                &DateTimeAsMicroseconds::now(),
+                "127.0.0.1",
+                &create_claims(&["admin"])
+            )
+            .is_ok());
+
+        assert!(expression
+            .check(
+                &DateTimeAsMicroseconds::now(),
+                "127.0.0.1",
+                &create_claims(&["support", "read-only"])
+            )
+            .is_ok());
+
+        assert_eq!(
+            Err(AuthorizationDenialReason::ClaimIsMissing(
+                "admin".to_string()
+            )),
+            expression.check(
+                &DateTimeAsMicroseconds::now(),
+                "127.0.0.1",
+                &create_claims(&["support"])
+            )
+        );
+    }
+
+    #[test]
+    fn test_empty_any_of_is_not_satisfied() {
+        assert!(ClaimsExpression::any_of(vec![]).is_err());
+
+        let expression = ClaimsExpression::AnyOf(vec![]);
+        assert!(!expression.is_empty());
+        assert!(expression
+            .check(
+                &DateTimeAsMicroseconds::now(),
+                "127.0.0.1",
+                &create_claims(&["admin"])
+            )
+            .is_err());
+
+        assert!(ClaimsExpression::all_of(vec![]).is_empty());
+        assert!(!ClaimsExpression::all_of(vec![ClaimsExpression::AnyOf(vec![])]).is_empty());
+    }
+
+    #[test]
+    fn test_not() {
+        let expression = ClaimsExpression::parse("user & !guest").unwrap();
+
+        assert!(expression
+            .check(
+                &DateTimeAsMicroseconds::now(),
+                "127.0.0.1",
+                &create_claims(&["user"])
+            )
+            .is_ok());
+
+        assert_eq!(
+            Err(AuthorizationDenialReason::ForbiddenClaims(
+                "!guest".to_string()
+            )),
+            expression.check(
+                &DateTimeAsMicroseconds::now(),
+                "127.0.0.1",
+                &create_claims(&["user", "guest"])
+            )
+        );
+    }
+
+    #[test]
+    fn test_ip_is_not_allowed() {
+        let allowed_ips = vec!["10.0.0.1".to_string()];
+
+        let claims = vec![RequestClaim {
+            id: "admin",
+            expires: in_future(),
+            allowed_ips: Some(&allowed_ips),
+        }];
+
+        let expression = ClaimsExpression::parse("admin | support").unwrap();
+
+        assert!(expression
+            .check(&DateTimeAsMicroseconds::now(), "10.0.0.1", &claims)
+            .is_ok());
+        assert_eq!(
+            Err(AuthorizationDenialReason::IpIsNotAllowed {
+                claim: "admin".to_string(),
+                ip: "127.0.0.1".to_string(),
+            }),
+            expression.check(&DateTimeAsMicroseconds::now(), "127.0.0.1", &claims)
+        );
+    }
+
+    #[test]
+    fn test_expired_claim() {
+        let now = DateTimeAsMicroseconds::now();
+
+        let claims = vec![RequestClaim {
+            id: "admin",
+            expires: DateTimeAsMicroseconds::new(now.unix_microseconds - 1),
+            allowed_ips: None,
+        }];
+
+        let expression = ClaimsExpression::parse("admin").unwrap();
+
+        assert_eq!(
+            Err(AuthorizationDenialReason::ClaimIsExpired(
+                "admin".to_string()
+            )),
+            expression.check(&now, "127.0.0.1", &claims)
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use hyper::Method;
+use my_http_server::HttpPath;
+
+use super::actions::{HttpAction, HttpActions};
+
+/// Snapshot of all registered actions. ControllersMiddleware swaps the whole snapshot on every update,
+/// so requests which are in flight keep working with the tables they started with.
+#[derive(Clone)]
+pub struct ControllersActions {
+    pub get: HttpActions,
+    pub post: HttpActions,
+    pub put: HttpActions,
+    pub delete: HttpActions,
+    pub patch: HttpActions,
+    pub head: HttpActions,
+    pub options: HttpActions,
+}
+
+impl ControllersActions {
+    pub fn new() -> Self {
+        Self {
+            get: HttpActions::new(),
+            post: HttpActions::new(),
+            put: HttpActions::new(),
+            delete: HttpActions::new(),
+            patch: HttpActions::new(),
+            head: HttpActions::new(),
+            options: HttpActions::new(),
+        }
+    }
+
+    pub fn get_http_actions(&self, method: &Method) -> Option<&HttpActions> {
+        match method {
+            &Method::GET => Some(&self.get),
+            &Method::POST => Some(&self.post),
+            &Method::PUT => Some(&self.put),
+            &Method::DELETE => Some(&self.delete),
+            &Method::PATCH => Some(&self.patch),
+            &Method::HEAD => Some(&self.head),
+            &Method::OPTIONS => Some(&self.options),
+            _ => None,
+        }
+    }
+
+    pub fn get_http_actions_mut(&mut self, method: &Method) -> Result<&mut HttpActions, String> {
+        match method {
+            &Method::GET => Ok(&mut self.get),
+            &Method::POST => Ok(&mut self.post),
+            &Method::PUT => Ok(&mut self.put),
+            &Method::DELETE => Ok(&mut self.delete),
+            &Method::PATCH => Ok(&mut self.patch),
+            &Method::HEAD => Ok(&mut self.head),
+            &Method::OPTIONS => Ok(&mut self.options),
+            _ => Err(format!("Method {} is not supported", method)),
+        }
+    }
+
+    pub fn register(&mut self, method: &Method, http_action: HttpAction) -> Result<(), String> {
+        self.get_http_actions_mut(method)?
+            .register(http_action)
+            .map_err(|err| format!("[{}]: {}", method, err))
+    }
+
+    pub fn unregister(&mut self, method: &Method, route: &str) -> Result<(), String> {
+        match self.get_http_actions_mut(method)?.unregister(route) {
+            Some(_) => Ok(()),
+            None => Err(format!("[{}]: Route {} is not registered", method, route)),
+        }
+    }
+
+    pub fn set_enabled(
+        &mut self,
+        method: &Method,
+        route: &str,
+        enabled: bool,
+    ) -> Result<(), String> {
+        match self
+            .get_http_actions_mut(method)?
+            .set_enabled(route, enabled)
+        {
+            Some(_) => Ok(()),
+            None => Err(format!("[{}]: Route {} is not registered", method, route)),
+        }
+    }
+
+    pub fn get_allowed_methods(&self, path: &HttpPath) -> Vec<Method> {
+        let mut result = Vec::new();
+
+        if self.get.find_action(path).is_some() {
+            result.push(Method::GET);
+        }
+
+        if self.post.find_action(path).is_some() {
+            result.push(Method::POST);
+        }
+
+        if self.put.find_action(path).is_some() {
+            result.push(Method::PUT);
+        }
+
+        if self.delete.find_action(path).is_some() {
+            result.push(Method::DELETE);
+        }
+
+        if self.patch.find_action(path).is_some() {
+            result.push(Method::PATCH);
+        }
+
+        if self.head.find_action(path).is_some() || self.get.find_action(path).is_some() {
+            result.push(Method::HEAD);
+        }
+
+        if result.len() > 0 {
+            result.push(Method::OPTIONS);
+        }
+
+        result
+    }
+}
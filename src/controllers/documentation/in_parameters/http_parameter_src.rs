@@ -9,6 +9,13 @@ pub enum HttpParameterInputSource {
 }
 
 impl HttpParameterInputSource {
+    pub fn is_path(&self) -> bool {
+        match self {
+            HttpParameterInputSource::Path => true,
+            _ => false,
+        }
+    }
+
     pub fn is_query(&self) -> bool {
         match self {
             HttpParameterInputSource::Query => true,
@@ -72,7 +72,7 @@ impl HttpParameters {
         }
     }
 
-    pub fn check_parameters(&self, method: &Method, route: &str) {
+    pub fn check_parameters(&self, method: &Method, route: &str) -> Result<(), String> {
         let mut found_body_param = None;
         if let Some(body_params) = &self.body_params {
             for body_param in body_params {
@@ -92,24 +92,34 @@ impl HttpParameters {
             match method {
                 &Method::GET => {
                     if self.body_params.is_some() {
-                        panic!(
+                        return Err(format!(
                             "GET method cannot have body parameters. Please check param {} for route {}",
                             found_body_param.field.name,
                             route
-                        );
+                        ));
+                    }
+                }
+                &Method::HEAD => {
+                    if self.body_params.is_some() {
+                        return Err(format!(
+                            "HEAD method cannot have body parameters. Please check param {} for route {}",
+                            found_body_param.field.name, route
+                        ));
                     }
                 }
                 &Method::DELETE => {
                     if self.body_params.is_some() {
-                        panic!(
+                        return Err(format!(
                             "DELETE method cannot have body parameters. Please check param {} for route {}",
                             found_body_param.field.name, route
-                        );
+                        ));
                     }
                 }
                 _ => {}
             }
         }
+
+        Ok(())
     }
 }
 
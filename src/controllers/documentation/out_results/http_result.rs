@@ -10,6 +10,32 @@ pub struct HttpResult {
     pub data_type: HttpDataType,
 }
 
+pub const TEXT_CONTENT_TYPE: &str = "text/plain";
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+pub const OCTET_STREAM_CONTENT_TYPE: &str = "application/octet-stream";
+
+impl HttpResult {
+    /// Media type the result is served with. None if result has no content.
+    /// Custom media type is set per action: see `ControllersMiddleware::set_result_content_type`
+    pub fn get_content_type(&self) -> Option<&'static str> {
+        match &self.data_type {
+            HttpDataType::None => None,
+            HttpDataType::SimpleType(simple_type) => {
+                if simple_type.is_binary() {
+                    Some(OCTET_STREAM_CONTENT_TYPE)
+                } else {
+                    Some(TEXT_CONTENT_TYPE)
+                }
+            }
+            HttpDataType::Object(_)
+            | HttpDataType::ArrayOf(_)
+            | HttpDataType::DictionaryOf(_)
+            | HttpDataType::DictionaryOfArray(_)
+            | HttpDataType::Enum(_) => Some(JSON_CONTENT_TYPE),
+        }
+    }
+}
+
 impl IntoHttpResult for HttpObjectStructure {
     fn into_http_result_object(
         self,
@@ -39,3 +65,39 @@ impl IntoHttpResult for HttpObjectStructure {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controllers::documentation::data_types::HttpSimpleType;
+
+    fn create_result(data_type: HttpDataType) -> HttpResult {
+        HttpResult {
+            http_code: 200,
+            nullable: false,
+            description: "Ok".to_string(),
+            data_type,
+        }
+    }
+
+    #[test]
+    fn test_content_type_by_data_type() {
+        assert_eq!(create_result(HttpDataType::None).get_content_type(), None);
+
+        assert_eq!(
+            create_result(HttpDataType::SimpleType(HttpSimpleType::String)).get_content_type(),
+            Some(TEXT_CONTENT_TYPE)
+        );
+
+        assert_eq!(
+            create_result(HttpDataType::SimpleType(HttpSimpleType::Binary)).get_content_type(),
+            Some(OCTET_STREAM_CONTENT_TYPE)
+        );
+
+        let object = HttpObjectStructure::new("Model", None);
+        assert_eq!(
+            create_result(object.into_http_data_type_array()).get_content_type(),
+            Some(JSON_CONTENT_TYPE)
+        );
+    }
+}
@@ -1,4 +1,6 @@
 mod http_result;
 mod into_http_result;
-pub use http_result::HttpResult;
+pub use http_result::{
+    HttpResult, JSON_CONTENT_TYPE, OCTET_STREAM_CONTENT_TYPE, TEXT_CONTENT_TYPE,
+};
 pub use into_http_result::IntoHttpResult;
@@ -1,15 +1,25 @@
 use my_http_server::{HttpFailResult, HttpPath, InputParamValue};
 
-use super::HttpRouteSegment;
+use super::{HttpRouteKey, HttpRouteSegment, HttpRouteValues};
 
+#[derive(Clone)]
 pub struct HttpRoute {
     pub route: String,
     pub keys_amount: usize,
     segments: Vec<HttpRouteSegment>,
+    required_segments_amount: usize,
+    has_catch_all: bool,
 }
 
 impl HttpRoute {
     pub fn new(route: &str) -> Self {
+        match Self::try_new(route) {
+            Ok(result) => result,
+            Err(err) => panic!("Invalid route '{}'. Err: {}", route, err),
+        }
+    }
+
+    pub fn try_new(route: &str) -> Result<Self, String> {
         let route_as_bytes = route.as_bytes();
 
         let mut keys_amount = 0;
@@ -24,7 +34,7 @@ impl HttpRoute {
             if last_element == b'/' {
                 if let Some(prev_pos) = prev_pos {
                     let segment = &route_as_bytes[prev_pos + 1..pos];
-                    let segment = HttpRouteSegment::new(segment);
+                    let segment = HttpRouteSegment::new(segment)?;
 
                     if segment.is_key() {
                         keys_amount += 1;
@@ -40,7 +50,7 @@ impl HttpRoute {
         if last_element != b'/' {
             if let Some(prev_pos) = prev_pos {
                 let segment = &route_as_bytes[prev_pos + 1..];
-                let segment = HttpRouteSegment::new(segment);
+                let segment = HttpRouteSegment::new(segment)?;
 
                 if segment.is_key() {
                     keys_amount += 1;
@@ -50,15 +60,38 @@ impl HttpRoute {
             }
         }
 
-        Self {
+        check_optional_and_catch_all_keys(&segments)?;
+
+        let required_segments_amount = segments
+            .iter()
+            .filter(|segment| match segment {
+                HttpRouteSegment::Key(key) => !key.optional && !key.catch_all,
+                HttpRouteSegment::Segment(_) => true,
+            })
+            .count();
+
+        let has_catch_all = match segments.last() {
+            Some(HttpRouteSegment::Key(key)) => key.catch_all,
+            _ => false,
+        };
+
+        Ok(Self {
             keys_amount,
             segments,
+            required_segments_amount,
+            has_catch_all,
             route: route.to_string(),
-        }
+        })
     }
 
     pub fn is_my_path(&self, path: &HttpPath) -> bool {
-        if path.segments_amount() != self.segments.len() {
+        let segments_amount = path.segments_amount();
+
+        if segments_amount < self.required_segments_amount {
+            return false;
+        }
+
+        if !self.has_catch_all && segments_amount > self.segments.len() {
             return false;
         }
 
@@ -68,8 +101,27 @@ impl HttpRoute {
 
         let mut index = 0;
         for segment in &self.segments {
+            if index >= segments_amount {
+                break;
+            }
+
             match segment {
-                HttpRouteSegment::Key(_) => {}
+                HttpRouteSegment::Key(key) => {
+                    if key.catch_all {
+                        return true;
+                    }
+
+                    if key.constraint.is_some() {
+                        match path.get_segment_value_as_str(index) {
+                            Some(value) => {
+                                if !key.is_match(value) {
+                                    return false;
+                                }
+                            }
+                            None => return false,
+                        }
+                    }
+                }
                 HttpRouteSegment::Segment(value) => {
                     if !path.has_value_at_index_case_insensitive(index, value) {
                         return false;
@@ -102,13 +154,33 @@ impl HttpRoute {
         for segment in &self.segments {
             match segment {
                 HttpRouteSegment::Key(segment_key) => {
-                    if segment_key == key {
-                        match path.get_segment_value_as_str(index) {
-                            Some(value) => return Ok(InputParamValue::Raw { value, src: "path" }),
-                            None => {
-                                panic!("Should not be here");
-                            }
+                    if segment_key.name == key {
+                        if segment_key.catch_all {
+                            return Ok(InputParamValue::Raw {
+                                value: get_path_tail(path, index),
+                                src: "path",
+                            });
+                        }
+
+                        if let Some(value) = path.get_segment_value_as_str(index) {
+                            return Ok(InputParamValue::Raw { value, src: "path" });
                         }
+
+                        if let Some(default_value) = &segment_key.default_value {
+                            return Ok(InputParamValue::Raw {
+                                value: default_value.as_str(),
+                                src: "path",
+                            });
+                        }
+
+                        return Err(HttpFailResult::new(
+                            my_http_server::WebContentType::Text,
+                            400,
+                            format!("Route {} has no value for key {}", self.route, key)
+                                .into_bytes(),
+                            false,
+                            true,
+                        ));
                     }
                 }
                 HttpRouteSegment::Segment(_) => {}
@@ -126,11 +198,139 @@ impl HttpRoute {
         ));
     }
 
+    /// Values of all keys which have value in the path or default value
+    pub fn get_values<'s>(&'s self, path: &'s HttpPath) -> HttpRouteValues<'s> {
+        let mut result = HttpRouteValues::new();
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            if let HttpRouteSegment::Key(key) = segment {
+                if key.catch_all {
+                    result.add(key.name.as_str(), get_path_tail(path, index));
+                    continue;
+                }
+
+                if let Some(value) = path.get_segment_value_as_str(index) {
+                    result.add(key.name.as_str(), value);
+                    continue;
+                }
+
+                if let Some(default_value) = &key.default_value {
+                    result.add(key.name.as_str(), default_value.as_str());
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn get_segments(&self) -> &[HttpRouteSegment] {
+        &self.segments
+    }
+
+    pub fn get_key(&self, key: &str) -> Option<&HttpRouteKey> {
+        for segment in &self.segments {
+            if let HttpRouteSegment::Key(segment_key) = segment {
+                if segment_key.name == key {
+                    return Some(segment_key);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Missing optional keys without default value have no value.
+    pub fn has_value(&self, path: &HttpPath, key: &str) -> bool {
+        let mut index = 0;
+        for segment in &self.segments {
+            if let HttpRouteSegment::Key(segment_key) = segment {
+                if segment_key.name == key {
+                    return segment_key.catch_all
+                        || index < path.segments_amount()
+                        || segment_key.default_value.is_some();
+                }
+            }
+
+            index += 1;
+        }
+
+        false
+    }
+
+    /// Route template without constraints the way OpenAPI expects it: /items/{id:int} -> /items/{id}
+    pub fn get_open_api_route(&self) -> String {
+        let mut result = String::with_capacity(self.route.len());
+
+        for (index, segment) in self.route.split('/').enumerate() {
+            if index > 0 {
+                result.push('/');
+            }
+
+            if segment.len() >= 2 && segment.starts_with('{') && segment.ends_with('}') {
+                let key = &segment[1..segment.len() - 1];
+                let key = key.strip_prefix('*').unwrap_or(key);
+
+                let name = match key.find(|c| c == ':' || c == '?' || c == '=') {
+                    Some(pos) => &key[..pos],
+                    None => key,
+                };
+
+                result.push('{');
+                result.push_str(name);
+                result.push('}');
+            } else {
+                result.push_str(segment);
+            }
+        }
+
+        result
+    }
+
+    /// OpenAPI has no optional path parameters, so every arity of the route is a separate path:
+    /// /reports/{year}/{month?} -> /reports/{year}, /reports/{year}/{month}. Each path comes with the keys it omits
+    pub fn get_open_api_routes(&self) -> Vec<(String, Vec<&str>)> {
+        let open_api_route = self.get_open_api_route();
+
+        let first_optional = self.segments.iter().position(|segment| match segment {
+            HttpRouteSegment::Key(key) => key.optional,
+            HttpRouteSegment::Segment(_) => false,
+        });
+
+        let Some(first_optional) = first_optional else {
+            return vec![(open_api_route, Vec::new())];
+        };
+
+        let open_api_segments: Vec<&str> = open_api_route
+            .split('/')
+            .skip(1)
+            .take(self.segments.len())
+            .collect();
+
+        let mut result = Vec::with_capacity(self.segments.len() - first_optional + 1);
+
+        for segments_amount in first_optional..=self.segments.len() {
+            let omitted_keys = self.segments[segments_amount..]
+                .iter()
+                .filter_map(|segment| match segment {
+                    HttpRouteSegment::Key(key) => Some(key.name.as_str()),
+                    HttpRouteSegment::Segment(_) => None,
+                })
+                .collect();
+
+            result.push((
+                format!("/{}", open_api_segments[..segments_amount].join("/")),
+                omitted_keys,
+            ));
+        }
+
+        result
+    }
+
     pub fn has_route_key(&self, key: &str) -> bool {
         for segment in &self.segments {
             match segment {
                 HttpRouteSegment::Key(segment_key) => {
-                    if segment_key == key {
+                    if segment_key.name == key {
                         return true;
                     }
                 }
@@ -141,6 +341,37 @@ impl HttpRoute {
         false
     }
 
+    /// Routes are conflicting if some path matches both of them
+    /// and literal segments do not make one of them more specific than another.
+    /// Optional keys are checked for every amount of segments the route can match.
+    /// Catch-all routes conflict only with catch-all routes of the same shape.
+    pub fn is_conflicting_with(&self, other: &HttpRoute) -> bool {
+        match (self.has_catch_all, other.has_catch_all) {
+            (true, true) => {
+                if self.segments.len() != other.segments.len() {
+                    return false;
+                }
+
+                let len = self.segments.len() - 1;
+                segments_are_conflicting(&self.segments[..len], &other.segments[..len])
+            }
+            (false, false) => {
+                for len in self.required_segments_amount..=self.segments.len() {
+                    if len < other.required_segments_amount || len > other.segments.len() {
+                        continue;
+                    }
+
+                    if segments_are_conflicting(&self.segments[..len], &other.segments[..len]) {
+                        return true;
+                    }
+                }
+
+                false
+            }
+            _ => false,
+        }
+    }
+
     pub fn check_route_keys(&self, route_keys: &Vec<&'static str>) -> Result<(), String> {
         for key in route_keys {
             if !self.has_route_key(key) {
@@ -155,6 +386,113 @@ impl HttpRoute {
     }
 }
 
+fn segments_are_conflicting(
+    self_segments: &[HttpRouteSegment],
+    other_segments: &[HttpRouteSegment],
+) -> bool {
+    let mut self_is_more_specific = false;
+    let mut other_is_more_specific = false;
+    // Different constraints which may match the same value: lookup would depend on registration order
+    let mut constraints_overlap = false;
+
+    for (self_segment, other_segment) in self_segments.iter().zip(other_segments.iter()) {
+        match (self_segment, other_segment) {
+            (HttpRouteSegment::Segment(self_value), HttpRouteSegment::Segment(other_value)) => {
+                if self_value != other_value {
+                    return false;
+                }
+            }
+            (HttpRouteSegment::Segment(value), HttpRouteSegment::Key(key)) => {
+                if !key.is_match(value) {
+                    return false;
+                }
+                self_is_more_specific = true;
+            }
+            (HttpRouteSegment::Key(key), HttpRouteSegment::Segment(value)) => {
+                if !key.is_match(value) {
+                    return false;
+                }
+                other_is_more_specific = true;
+            }
+            (HttpRouteSegment::Key(self_key), HttpRouteSegment::Key(other_key)) => {
+                match (&self_key.constraint, &other_key.constraint) {
+                    (Some(self_constraint), Some(other_constraint)) => {
+                        if self_constraint.is_disjoint_with(other_constraint) {
+                            return false;
+                        }
+
+                        if !self_constraint.is_same_as(other_constraint) {
+                            constraints_overlap = true;
+                        }
+                    }
+                    (Some(_), None) => {
+                        self_is_more_specific = true;
+                    }
+                    (None, Some(_)) => {
+                        other_is_more_specific = true;
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+    }
+
+    constraints_overlap || self_is_more_specific == other_is_more_specific
+}
+
+fn check_optional_and_catch_all_keys(segments: &[HttpRouteSegment]) -> Result<(), String> {
+    let mut optional_key = None;
+
+    for (index, segment) in segments.iter().enumerate() {
+        if let HttpRouteSegment::Key(key) = segment {
+            if key.catch_all && index != segments.len() - 1 {
+                return Err(format!(
+                    "Catch-all key '{}' must be the last segment",
+                    key.name
+                ));
+            }
+
+            if key.optional {
+                optional_key = Some(key.name.as_str());
+                continue;
+            }
+        }
+
+        if let Some(optional_key) = optional_key {
+            return Err(format!(
+                "Optional key '{}' can be followed only by optional keys",
+                optional_key
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rest of the path starting from the segment with index. Segments are looked up one by one in the path,
+/// so the tail is a slice of the path itself
+fn get_path_tail<'s>(path: &'s HttpPath, index: usize) -> &'s str {
+    let full_path = path.as_str();
+    let mut start = 0;
+
+    for segment_index in 0..index {
+        let Some(segment) = path.get_segment_value_as_str(segment_index) else {
+            return "";
+        };
+
+        match full_path[start..].find(segment) {
+            Some(pos) => start += pos + segment.len(),
+            None => return "",
+        }
+    }
+
+    if path.get_segment_value_as_str(index).is_none() {
+        return "";
+    }
+
+    full_path[start..].trim_matches('/')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,4 +571,220 @@ mod tests {
         assert_eq!(route.has_route_key("Key"), true);
         assert_eq!(route.has_route_key("key"), false);
     }
+
+    #[test]
+    fn test_conflicting_routes() {
+        let route = HttpRoute::new("/api/{id}");
+
+        assert!(route.is_conflicting_with(&HttpRoute::new("/api/{name}")));
+        assert!(route.is_conflicting_with(&HttpRoute::new("/API/{id}")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/api/info")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/api/{id}/info")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/test/{id}")));
+
+        let route = HttpRoute::new("/api/{id}/info");
+        assert!(route.is_conflicting_with(&HttpRoute::new("/api/info/{id}")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/api/details/info/")));
+    }
+
+    #[test]
+    fn test_route_constraints() {
+        let route = HttpRoute::new("/items/{id:int}");
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/items/15")), true);
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/items/abc")), false);
+
+        let route = HttpRoute::new("/items/{id:guid}");
+        assert_eq!(
+            route.is_my_path(&HttpPath::from_str(
+                "/items/6f9619ff-8b86-d011-b42d-00cf4fc964ff"
+            )),
+            true
+        );
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/items/15")), false);
+
+        let route = HttpRoute::new("/items/{name:alpha}");
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/items/abc")), true);
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/items/abc1")), false);
+
+        let route = HttpRoute::new("/items/{x:regex(^a.*)}");
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/items/abc")), true);
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/items/bcd")), false);
+
+        assert_eq!(route.has_route_key("x"), true);
+        assert_eq!(route.get_open_api_route(), "/items/{x}");
+    }
+
+    #[test]
+    fn test_unknown_route_constraint() {
+        assert!(HttpRoute::try_new("/items/{id:unknown}").is_err());
+        assert!(HttpRoute::try_new("/items/{id:regex(()}").is_err());
+    }
+
+    #[test]
+    fn test_constrained_routes_are_not_conflicting() {
+        let route = HttpRoute::new("/items/{id:int}");
+
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/items/{name}")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/items/{name:alpha}")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/items/info")));
+        assert!(route.is_conflicting_with(&HttpRoute::new("/items/{value:int}")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/items/{id:guid}")));
+        assert!(!HttpRoute::new("/items/{id:guid}")
+            .is_conflicting_with(&HttpRoute::new("/items/{name:alpha}")));
+    }
+
+    #[test]
+    fn test_overlapping_constraints_are_conflicting() {
+        let route = HttpRoute::new("/items/{id:int}");
+        assert!(route.is_conflicting_with(&HttpRoute::new("/items/{id:long}")));
+        assert!(route.is_conflicting_with(&HttpRoute::new("/items/{id:regex(^[0-9]+$)}")));
+        assert!(route.is_conflicting_with(&HttpRoute::new("/items/{date:datetime}")));
+
+        let route = HttpRoute::new("/items/{x:regex(^a.*)}");
+        assert!(route.is_conflicting_with(&HttpRoute::new("/items/{x:alpha}")));
+        assert!(route.is_conflicting_with(&HttpRoute::new("/items/{x:regex(^ab.*)}")));
+
+        // Disjoint segment elsewhere in the route makes them distinct
+        assert!(!HttpRoute::new("/items/{id:int}/info")
+            .is_conflicting_with(&HttpRoute::new("/items/{id:long}/details")));
+    }
+
+    #[test]
+    fn test_catch_all() {
+        let route = HttpRoute::new("/files/{*path}");
+
+        let path = HttpPath::from_str("/files/folder/file.txt");
+        assert_eq!(route.is_my_path(&path), true);
+        assert_eq!(
+            route
+                .get_value(&path, "path")
+                .unwrap()
+                .get_raw_str()
+                .unwrap(),
+            "folder/file.txt"
+        );
+
+        let route = HttpRoute::new("/api/{name}/files/{*path}");
+        let path = HttpPath::from_str("/api/files/files/folder/files/");
+        assert_eq!(
+            route
+                .get_value(&path, "path")
+                .unwrap()
+                .get_raw_str()
+                .unwrap(),
+            "folder/files"
+        );
+
+        // Catch-all does not cover the prefix segments
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/files")), false);
+
+        let route = HttpRoute::new("/files/{*path}");
+
+        // Empty catch-all
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/files")), true);
+
+        assert_eq!(
+            route.is_my_path(&HttpPath::from_str("/other/file.txt")),
+            false
+        );
+        assert_eq!(route.get_open_api_route(), "/files/{path}");
+    }
+
+    #[test]
+    fn test_optional_keys() {
+        let route = HttpRoute::new("/reports/{year}/{month?}/{day=1}");
+
+        assert_eq!(route.is_my_path(&HttpPath::from_str("/reports")), false);
+        assert_eq!(
+            route.is_my_path(&HttpPath::from_str("/reports/2023/5/6/7")),
+            false
+        );
+
+        let path = HttpPath::from_str("/reports/2023");
+        assert_eq!(route.is_my_path(&path), true);
+        assert_eq!(route.has_value(&path, "month"), false);
+        assert!(route.get_value(&path, "month").is_err());
+        assert_eq!(
+            route
+                .get_value(&path, "day")
+                .unwrap()
+                .get_raw_str()
+                .unwrap(),
+            "1"
+        );
+
+        let path = HttpPath::from_str("/reports/2023/5");
+        assert_eq!(route.is_my_path(&path), true);
+        assert_eq!(
+            route
+                .get_value(&path, "month")
+                .unwrap()
+                .get_raw_str()
+                .unwrap(),
+            "5"
+        );
+
+        assert_eq!(route.get_open_api_route(), "/reports/{year}/{month}/{day}");
+    }
+
+    #[test]
+    fn test_open_api_routes_of_optional_keys() {
+        let route = HttpRoute::new("/reports/{year:int}/{month?}/{day=1}");
+
+        let routes = route.get_open_api_routes();
+
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0].0, "/reports/{year}");
+        assert_eq!(routes[0].1, vec!["month", "day"]);
+        assert_eq!(routes[1].0, "/reports/{year}/{month}");
+        assert_eq!(routes[1].1, vec!["day"]);
+        assert_eq!(routes[2].0, "/reports/{year}/{month}/{day}");
+        assert_eq!(routes[2].1.len(), 0);
+
+        let route = HttpRoute::new("/Items/{id}");
+        let routes = route.get_open_api_routes();
+
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].0, "/Items/{id}");
+    }
+
+    #[test]
+    fn test_default_value_has_to_match_constraint() {
+        assert!(HttpRoute::try_new("/reports/{page:int=1}").is_ok());
+        assert!(HttpRoute::try_new("/reports/{page:int=first}").is_err());
+        assert!(HttpRoute::try_new("/reports/{page=first}").is_ok());
+    }
+
+    #[test]
+    fn test_get_values() {
+        let route = HttpRoute::new("/accounts/{accountId}/{page=1}/{month?}");
+
+        let path = HttpPath::from_str("/accounts/acc-1");
+        let values = route.get_values(&path);
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values.get("accountId"), Some("acc-1"));
+        assert_eq!(values.get("page"), Some("1"));
+        assert_eq!(values.get("month"), None);
+    }
+
+    #[test]
+    fn test_invalid_optional_and_catch_all_keys() {
+        assert!(HttpRoute::try_new("/reports/{year?}/{month}").is_err());
+        assert!(HttpRoute::try_new("/files/{*path}/info").is_err());
+        assert!(HttpRoute::try_new("/files/{name?}/{*path}").is_err());
+    }
+
+    #[test]
+    fn test_optional_and_catch_all_conflicts() {
+        let route = HttpRoute::new("/reports/{year}/{month?}");
+
+        assert!(route.is_conflicting_with(&HttpRoute::new("/reports/{id}")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/reports")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/reports/{year}/summary")));
+
+        let route = HttpRoute::new("/files/{*path}");
+        assert!(route.is_conflicting_with(&HttpRoute::new("/files/{*other}")));
+        assert!(!route.is_conflicting_with(&HttpRoute::new("/files/{name}")));
+    }
 }
@@ -1,23 +1,247 @@
+use regex::Regex;
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+#[derive(Debug, Clone)]
+pub enum HttpRouteConstraint {
+    Int,
+    Long,
+    Guid,
+    DateTime,
+    Alpha,
+    Regex(Regex),
+}
+
+impl HttpRouteConstraint {
+    pub fn parse(src: &str) -> Result<Self, String> {
+        match src {
+            "int" => Ok(Self::Int),
+            "long" => Ok(Self::Long),
+            "guid" => Ok(Self::Guid),
+            "datetime" => Ok(Self::DateTime),
+            "alpha" => Ok(Self::Alpha),
+            _ => {
+                if src.starts_with("regex(") && src.ends_with(')') {
+                    let pattern = &src[6..src.len() - 1];
+
+                    return match Regex::new(pattern) {
+                        Ok(regex) => Ok(Self::Regex(regex)),
+                        Err(err) => Err(format!(
+                            "Invalid regex route constraint '{}'. Err: {}",
+                            pattern, err
+                        )),
+                    };
+                }
+
+                Err(format!("Unknown route constraint '{}'", src))
+            }
+        }
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        match self {
+            HttpRouteConstraint::Int => value.parse::<i32>().is_ok(),
+            HttpRouteConstraint::Long => value.parse::<i64>().is_ok(),
+            HttpRouteConstraint::Guid => is_guid(value),
+            HttpRouteConstraint::DateTime => DateTimeAsMicroseconds::from_str(value).is_some(),
+            HttpRouteConstraint::Alpha => {
+                value.len() > 0 && value.chars().all(|c| c.is_ascii_alphabetic())
+            }
+            HttpRouteConstraint::Regex(regex) => regex.is_match(value),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            HttpRouteConstraint::Int => "int",
+            HttpRouteConstraint::Long => "long",
+            HttpRouteConstraint::Guid => "guid",
+            HttpRouteConstraint::DateTime => "datetime",
+            HttpRouteConstraint::Alpha => "alpha",
+            HttpRouteConstraint::Regex(regex) => regex.as_str(),
+        }
+    }
+
+    pub fn is_same_as(&self, other: &HttpRouteConstraint) -> bool {
+        match (self, other) {
+            (HttpRouteConstraint::Regex(self_regex), HttpRouteConstraint::Regex(other_regex)) => {
+                self_regex.as_str() == other_regex.as_str()
+            }
+            (HttpRouteConstraint::Regex(_), _) => false,
+            (_, HttpRouteConstraint::Regex(_)) => false,
+            _ => self.as_str() == other.as_str(),
+        }
+    }
+
+    /// True only if no value can match both constraints. Overlap which can not be proven absent is treated as possible
+    pub fn is_disjoint_with(&self, other: &HttpRouteConstraint) -> bool {
+        match (self, other) {
+            (
+                HttpRouteConstraint::Int | HttpRouteConstraint::Long,
+                HttpRouteConstraint::Guid | HttpRouteConstraint::Alpha,
+            ) => true,
+            (
+                HttpRouteConstraint::Guid | HttpRouteConstraint::Alpha,
+                HttpRouteConstraint::Int | HttpRouteConstraint::Long,
+            ) => true,
+            (HttpRouteConstraint::Guid, HttpRouteConstraint::Alpha) => true,
+            (HttpRouteConstraint::Alpha, HttpRouteConstraint::Guid) => true,
+            _ => false,
+        }
+    }
+
+    pub fn as_swagger_type(&self) -> &str {
+        match self {
+            HttpRouteConstraint::Int => "integer",
+            HttpRouteConstraint::Long => "integer",
+            HttpRouteConstraint::Guid => "string",
+            HttpRouteConstraint::DateTime => "string",
+            HttpRouteConstraint::Alpha => "string",
+            HttpRouteConstraint::Regex(_) => "string",
+        }
+    }
+
+    pub fn as_format(&self) -> Option<&str> {
+        match self {
+            HttpRouteConstraint::Int => Some("int32"),
+            HttpRouteConstraint::Long => Some("int64"),
+            HttpRouteConstraint::Guid => Some("uuid"),
+            HttpRouteConstraint::DateTime => Some("date-time"),
+            HttpRouteConstraint::Alpha => None,
+            HttpRouteConstraint::Regex(_) => None,
+        }
+    }
+
+    pub fn as_pattern(&self) -> Option<&str> {
+        match self {
+            HttpRouteConstraint::Alpha => Some("^[a-zA-Z]+$"),
+            HttpRouteConstraint::Regex(regex) => Some(regex.as_str()),
+            _ => None,
+        }
+    }
+}
+
+fn is_guid(value: &str) -> bool {
+    let value = value.as_bytes();
+
+    if value.len() != 36 {
+        return false;
+    }
+
+    for (index, b) in value.iter().enumerate() {
+        match index {
+            8 | 13 | 18 | 23 => {
+                if *b != b'-' {
+                    return false;
+                }
+            }
+            _ => {
+                if !b.is_ascii_hexdigit() {
+                    return false;
+                }
+            }
+        }
+    }
+
+    true
+}
+
+#[derive(Clone)]
+pub struct HttpRouteKey {
+    pub name: String,
+    pub constraint: Option<HttpRouteConstraint>,
+    pub optional: bool,
+    pub default_value: Option<String>,
+    pub catch_all: bool,
+}
+
+impl HttpRouteKey {
+    /// Parses key content without braces: id, id:int, month?, month=1, *path
+    pub fn parse(src: &str) -> Result<Self, String> {
+        if let Some(name) = src.strip_prefix('*') {
+            return Ok(Self {
+                name: name.to_string(),
+                constraint: None,
+                optional: false,
+                default_value: None,
+                catch_all: true,
+            });
+        }
+
+        let mut src = src;
+        let mut optional = false;
+        let mut default_value = None;
+
+        if let Some(value) = src.strip_suffix('?') {
+            optional = true;
+            src = value;
+        } else if !src.ends_with(')') {
+            if let Some(pos) = src.rfind('=') {
+                optional = true;
+                default_value = Some(src[pos + 1..].to_string());
+                src = &src[..pos];
+            }
+        }
+
+        let (name, constraint) = match src.find(':') {
+            Some(pos) => (
+                &src[..pos],
+                Some(HttpRouteConstraint::parse(&src[pos + 1..])?),
+            ),
+            None => (src, None),
+        };
+
+        if let (Some(constraint), Some(default_value)) = (&constraint, &default_value) {
+            if !constraint.is_match(default_value) {
+                return Err(format!(
+                    "Default value '{}' of key '{}' does not match constraint '{}'",
+                    default_value,
+                    name,
+                    constraint.as_str()
+                ));
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            constraint,
+            optional,
+            default_value,
+            catch_all: false,
+        })
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        match &self.constraint {
+            Some(constraint) => constraint.is_match(value),
+            None => true,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub enum HttpRouteSegment {
-    Key(String),
+    Key(HttpRouteKey),
     Segment(String),
 }
 
 impl HttpRouteSegment {
-    pub fn new(value: &[u8]) -> Self {
+    pub fn new(value: &[u8]) -> Result<Self, String> {
         if value.len() < 2 {
-            return Self::Segment(std::str::from_utf8(value).unwrap().to_lowercase());
+            return Ok(Self::Segment(
+                std::str::from_utf8(value).unwrap().to_lowercase(),
+            ));
         }
 
         if value[0] == b'{' && value[value.len() - 1] == b'}' {
-            return Self::Key(
-                std::str::from_utf8(&value[1..value.len() - 1])
-                    .unwrap()
-                    .to_string(),
-            );
+            let key = std::str::from_utf8(&value[1..value.len() - 1]).unwrap();
+            let result = HttpRouteKey::parse(key)?;
+
+            return Ok(Self::Key(result));
         }
 
-        return Self::Segment(std::str::from_utf8(value).unwrap().to_lowercase());
+        return Ok(Self::Segment(
+            std::str::from_utf8(value).unwrap().to_lowercase(),
+        ));
     }
 
     pub fn is_key(&self) -> bool {
@@ -29,7 +253,7 @@ impl HttpRouteSegment {
 
     pub fn unwrap_as_key(&self) -> &str {
         match self {
-            HttpRouteSegment::Key(value) => value,
+            HttpRouteSegment::Key(value) => &value.name,
             _ => panic!("This segment is not a key"),
         }
     }
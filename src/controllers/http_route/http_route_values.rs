@@ -0,0 +1,33 @@
+/// Values of route keys extracted from the request path
+pub struct HttpRouteValues<'s> {
+    values: Vec<(&'s str, &'s str)>,
+}
+
+impl<'s> HttpRouteValues<'s> {
+    pub fn new() -> Self {
+        Self { values: Vec::new() }
+    }
+
+    pub fn add(&mut self, key: &'s str, value: &'s str) {
+        self.values.push((key, value));
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'s str> {
+        self.values
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(&'s str, &'s str)> {
+        self.values.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use my_http_server::HttpPath;
+
+use super::{HttpRoute, HttpRouteConstraint, HttpRouteSegment};
+
+#[derive(Clone)]
+pub struct HttpRoutesTree {
+    root: HttpRoutesTreeNode,
+}
+
+impl HttpRoutesTree {
+    pub fn new() -> Self {
+        Self {
+            root: HttpRoutesTreeNode::new(),
+        }
+    }
+
+    pub fn insert(&mut self, http_route: &HttpRoute, value: usize) {
+        let mut node = &mut self.root;
+
+        for segment in http_route.get_segments() {
+            if let HttpRouteSegment::Key(key) = segment {
+                if key.catch_all {
+                    if node.catch_all.is_none() {
+                        node.catch_all = Some(value);
+                    }
+
+                    return;
+                }
+
+                if key.optional && node.value.is_none() {
+                    node.value = Some(value);
+                }
+            }
+
+            node = match segment {
+                HttpRouteSegment::Key(key) => match &key.constraint {
+                    Some(constraint) => node.get_or_create_constrained_key(constraint),
+                    None => node
+                        .key
+                        .get_or_insert_with(|| Box::new(HttpRoutesTreeNode::new())),
+                },
+                HttpRouteSegment::Segment(value) => node
+                    .segments
+                    .entry(value.to_string())
+                    .or_insert_with(HttpRoutesTreeNode::new),
+            };
+        }
+
+        if node.value.is_none() {
+            node.value = Some(value);
+        }
+    }
+
+    pub fn find(&self, path: &HttpPath) -> Option<usize> {
+        self.root.find(path, 0, path.segments_amount())
+    }
+}
+
+#[derive(Clone)]
+struct HttpRoutesTreeNode {
+    segments: HashMap<String, HttpRoutesTreeNode>,
+    constrained_keys: Vec<(HttpRouteConstraint, HttpRoutesTreeNode)>,
+    key: Option<Box<HttpRoutesTreeNode>>,
+    value: Option<usize>,
+    catch_all: Option<usize>,
+}
+
+impl HttpRoutesTreeNode {
+    fn new() -> Self {
+        Self {
+            segments: HashMap::new(),
+            constrained_keys: Vec::new(),
+            key: None,
+            value: None,
+            catch_all: None,
+        }
+    }
+
+    fn get_or_create_constrained_key(
+        &mut self,
+        constraint: &HttpRouteConstraint,
+    ) -> &mut HttpRoutesTreeNode {
+        let index = match self
+            .constrained_keys
+            .iter()
+            .position(|(itm, _)| itm.is_same_as(constraint))
+        {
+            Some(index) => index,
+            None => {
+                self.constrained_keys
+                    .push((constraint.clone(), HttpRoutesTreeNode::new()));
+                self.constrained_keys.len() - 1
+            }
+        };
+
+        &mut self.constrained_keys[index].1
+    }
+
+    fn find(&self, path: &HttpPath, index: usize, segments_amount: usize) -> Option<usize> {
+        if index == segments_amount {
+            return self.value.or(self.catch_all);
+        }
+
+        let Some(segment) = path.get_segment_value_as_str(index) else {
+            return self.catch_all;
+        };
+
+        if let Some(node) = self.segments.get(segment.to_lowercase().as_str()) {
+            if let Some(result) = node.find(path, index + 1, segments_amount) {
+                return Some(result);
+            }
+        }
+
+        for (constraint, node) in &self.constrained_keys {
+            if constraint.is_match(segment) {
+                if let Some(result) = node.find(path, index + 1, segments_amount) {
+                    return Some(result);
+                }
+            }
+        }
+
+        if let Some(node) = &self.key {
+            if let Some(result) = node.find(path, index + 1, segments_amount) {
+                return Some(result);
+            }
+        }
+
+        self.catch_all
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_tree(routes: &[&str]) -> HttpRoutesTree {
+        let mut tree = HttpRoutesTree::new();
+
+        for (index, route) in routes.iter().enumerate() {
+            tree.insert(&HttpRoute::new(route), index);
+        }
+
+        tree
+    }
+
+    #[test]
+    fn test_root() {
+        let tree = create_tree(&["/", "/test"]);
+
+        assert_eq!(tree.find(&HttpPath::from_str("/")), Some(0));
+        assert_eq!(tree.find(&HttpPath::from_str("/test")), Some(1));
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        let tree = create_tree(&["/Test/{key}/Second"]);
+
+        assert_eq!(tree.find(&HttpPath::from_str("/test/1/second")), Some(0));
+        assert_eq!(tree.find(&HttpPath::from_str("/TEST/1/SECOND")), Some(0));
+        assert_eq!(tree.find(&HttpPath::from_str("/test/1")), None);
+        assert_eq!(tree.find(&HttpPath::from_str("/test/1/second/2")), None);
+    }
+
+    #[test]
+    fn test_literal_is_preferred_over_key() {
+        let tree = create_tree(&["/api/{id}", "/api/info"]);
+
+        assert_eq!(tree.find(&HttpPath::from_str("/api/info")), Some(1));
+        assert_eq!(tree.find(&HttpPath::from_str("/api/15")), Some(0));
+    }
+
+    #[test]
+    fn test_falls_back_to_key_if_literal_branch_does_not_match() {
+        let tree = create_tree(&["/api/info/details", "/api/{id}/items"]);
+
+        assert_eq!(tree.find(&HttpPath::from_str("/api/info/items")), Some(1));
+        assert_eq!(tree.find(&HttpPath::from_str("/api/info/details")), Some(0));
+    }
+
+    #[test]
+    fn test_constrained_key_is_preferred_over_key() {
+        let tree = create_tree(&["/items/{name}", "/items/{id:int}", "/items/info"]);
+
+        assert_eq!(tree.find(&HttpPath::from_str("/items/15")), Some(1));
+        assert_eq!(tree.find(&HttpPath::from_str("/items/abc")), Some(0));
+        assert_eq!(tree.find(&HttpPath::from_str("/items/info")), Some(2));
+    }
+
+    #[test]
+    fn test_catch_all_and_optional_keys() {
+        let tree = create_tree(&["/files/{*path}", "/files/info", "/reports/{year}/{month?}"]);
+
+        assert_eq!(tree.find(&HttpPath::from_str("/files/info")), Some(1));
+        assert_eq!(tree.find(&HttpPath::from_str("/files/a/b/c")), Some(0));
+        assert_eq!(tree.find(&HttpPath::from_str("/files")), Some(0));
+        assert_eq!(tree.find(&HttpPath::from_str("/reports/2023")), Some(2));
+        assert_eq!(tree.find(&HttpPath::from_str("/reports/2023/5")), Some(2));
+        assert_eq!(tree.find(&HttpPath::from_str("/reports")), None);
+    }
+
+    #[test]
+    fn test_same_as_linear_scan() {
+        let routes = [
+            "/",
+            "/api/v1/users",
+            "/api/v1/users/{id}",
+            "/api/v1/users/{id}/orders/{order_id}",
+            "/api/v1/orders/{id}",
+        ];
+
+        let tree = create_tree(&routes);
+
+        let http_routes: Vec<HttpRoute> = routes.iter().map(|itm| HttpRoute::new(itm)).collect();
+
+        for path in [
+            "/",
+            "/api/v1/users",
+            "/API/V1/Users/5",
+            "/api/v1/users/5/orders/6",
+            "/api/v1/orders/7",
+            "/api/v1/orders",
+            "/api/v2/users",
+        ] {
+            let path = HttpPath::from_str(path);
+            let linear = http_routes.iter().position(|itm| itm.is_my_path(&path));
+            assert_eq!(tree.find(&path), linear);
+        }
+    }
+}
@@ -1,4 +1,8 @@
 mod http_route;
 mod http_route_segment;
+mod http_route_values;
+mod http_routes_tree;
 pub use http_route::HttpRoute;
 pub use http_route_segment::*;
+pub use http_route_values::HttpRouteValues;
+pub use http_routes_tree::HttpRoutesTree;
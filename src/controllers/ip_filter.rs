@@ -0,0 +1,214 @@
+use std::net::IpAddr;
+
+use my_http_server::HttpRequest;
+
+const X_FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Ip address or CIDR range: `10.0.0.0/8`, `192.168.1.10`, `fd00::/8`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpNetwork {
+    pub address: IpAddr,
+    pub prefix_len: u8,
+}
+
+impl IpNetwork {
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let src = src.trim();
+
+        let (address, prefix_len) = match src.split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len)),
+            None => (src, None),
+        };
+
+        let address: IpAddr = address
+            .parse()
+            .map_err(|_| format!("Invalid ip address {}", src))?;
+
+        let max_prefix_len = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix_len| *prefix_len <= max_prefix_len)
+                .ok_or_else(|| format!("Invalid network prefix {}", src))?,
+            None => max_prefix_len,
+        };
+
+        Ok(Self {
+            address,
+            prefix_len,
+        })
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (&self.address, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = mask_u32(self.prefix_len);
+                u32::from(*network) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = mask_u128(self.prefix_len);
+                u128::from(*network) & mask == u128::from(*ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+fn mask_u32(prefix_len: u8) -> u32 {
+    if prefix_len == 0 {
+        return 0;
+    }
+
+    u32::MAX << (32 - prefix_len as u32)
+}
+
+fn mask_u128(prefix_len: u8) -> u128 {
+    if prefix_len == 0 {
+        return 0;
+    }
+
+    u128::MAX << (128 - prefix_len as u32)
+}
+
+/// Deny list wins. If allow list is not empty - ip has to be in it.
+#[derive(Debug, Clone)]
+pub struct IpFilter {
+    pub allow: Vec<IpNetwork>,
+    pub deny: Vec<IpNetwork>,
+}
+
+impl IpFilter {
+    pub fn new() -> Self {
+        Self {
+            allow: Vec::new(),
+            deny: Vec::new(),
+        }
+    }
+
+    pub fn allow(mut self, network: &str) -> Result<Self, String> {
+        self.allow.push(IpNetwork::parse(network)?);
+        Ok(self)
+    }
+
+    pub fn deny(mut self, network: &str) -> Result<Self, String> {
+        self.deny.push(IpNetwork::parse(network)?);
+        Ok(self)
+    }
+
+    pub fn is_allowed(&self, ip: &str) -> bool {
+        match ip.trim().parse::<IpAddr>() {
+            Ok(ip) => self.is_ip_allowed(&ip),
+            Err(_) => false,
+        }
+    }
+
+    pub fn is_ip_allowed(&self, ip: &IpAddr) -> bool {
+        if self.deny.iter().any(|network| network.contains(ip)) {
+            return false;
+        }
+
+        self.allow.len() == 0 || self.allow.iter().any(|network| network.contains(ip))
+    }
+}
+
+/// Proxies allowed to add addresses into X-Forwarded-For chain
+#[derive(Debug, Clone)]
+pub struct TrustedProxies {
+    pub networks: Vec<IpNetwork>,
+}
+
+impl TrustedProxies {
+    pub fn new() -> Self {
+        Self {
+            networks: Vec::new(),
+        }
+    }
+
+    pub fn add(mut self, network: &str) -> Result<Self, String> {
+        self.networks.push(IpNetwork::parse(network)?);
+        Ok(self)
+    }
+
+    fn is_trusted(&self, ip: &IpAddr) -> bool {
+        self.networks.iter().any(|network| network.contains(ip))
+    }
+
+    /// Socket peer is the client unless it is a trusted proxy. Only then X-Forwarded-For is walked from the nearest hop
+    /// and the first address which is not a trusted proxy is the client. Addresses to the left of it are set by the client.
+    pub fn resolve_client_ip(&self, peer_ip: IpAddr, x_forwarded_for: &[&str]) -> IpAddr {
+        let mut result = peer_ip;
+
+        if !self.is_trusted(&result) {
+            return result;
+        }
+
+        let hops = x_forwarded_for
+            .iter()
+            .flat_map(|header_value| header_value.split(','))
+            .rev();
+
+        for hop in hops {
+            // Garbage in the chain - the nearest trusted proxy is the last address we can rely on
+            let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+                return result;
+            };
+
+            result = ip;
+
+            if !self.is_trusted(&result) {
+                return result;
+            }
+        }
+
+        result
+    }
+
+    pub fn resolve_request_ip(&self, request: &HttpRequest) -> IpAddr {
+        let x_forwarded_for: Vec<&str> = request
+            .get_headers()
+            .get_all(X_FORWARDED_FOR_HEADER)
+            .iter()
+            .filter_map(|header_value| header_value.to_str().ok())
+            .collect();
+
+        self.resolve_client_ip(request.addr.ip(), &x_forwarded_for)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_networks() {
+        let network = IpNetwork::parse("10.1.0.0/16").unwrap();
+        assert!(network.contains(&"10.1.200.3".parse().unwrap()));
+        assert!(!network.contains(&"10.2.0.1".parse().unwrap()));
+        assert!(!network.contains(&"::1".parse().unwrap()));
+
+        let network = IpNetwork::parse("fd00::/8").unwrap();
+        assert!(network.contains(&"fd12::1".parse().unwrap()));
+
+        assert!(IpNetwork::parse("10.0.0.0/33").is_err());
+        assert!(IpNetwork::parse("localhost").is_err());
+    }
+
+    #[test]
+    fn test_ip_filter() {
+        let filter = IpFilter::new()
+            .allow("10.0.0.0/8")
+            .unwrap()
+            .deny("10.0.0.13")
+            .unwrap();
+
+        assert!(filter.is_allowed("10.20.30.40"));
+        assert!(!filter.is_allowed("10.0.0.13"));
+        assert!(!filter.is_allowed("8.8.8.8"));
+        assert!(!filter.is_allowed("not an ip"));
+    }
+}
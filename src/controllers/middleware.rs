@@ -1,30 +1,43 @@
+use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use hyper::Method;
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use my_http_server::{
-    HttpContext, HttpFailResult, HttpOkResult, HttpServerMiddleware, HttpServerRequestFlow,
+    HttpContext, HttpFailResult, HttpOkResult, HttpOutput, HttpPath, HttpServerMiddleware,
+    HttpServerRequestFlow,
 };
 
 use super::{
     actions::{
-        DeleteAction, GetAction, GetDescription, HandleHttpRequest, HttpAction, HttpActions,
-        PostAction, PutAction,
+        DeleteAction, GetAction, GetDescription, HandleHttpRequest, HeadAction, HttpAction,
+        HttpActions, OptionsAction, PatchAction, PostAction, PutAction,
     },
     documentation::{data_types::HttpObjectStructure, ShouldBeAuthorized},
-    AuthErrorFactory, AuthorizationMap, HttpRoute,
+    ActionFilter, AuthErrorFactory, AuthorizationMap, AuthorizationPolicy, ControllersActions,
+    HttpRoute, IpFilter, RouteGroup, TrustedProxies,
 };
 
 use super::ControllersAuthorization;
 
+/// Actions can be registered, unregistered and updated at runtime. They are kept in a snapshot which is swapped on change.
+/// Breaking change: `get`, `post`, `put`, `delete`, `patch`, `head` and `options` fields are replaced by [`Self::get_actions`],
+/// `list_of_*_route_actions` return a copy of the snapshot as `Vec<Arc<HttpAction>>` instead of `&Vec<HttpAction>`.
 pub struct ControllersMiddleware {
-    pub get: HttpActions,
-    pub post: HttpActions,
-    pub put: HttpActions,
-    pub delete: HttpActions,
+    actions: ArcSwap<ControllersActions>,
+    update_lock: Mutex<()>,
     pub http_objects: Vec<HttpObjectStructure>,
     pub authorization_map: AuthorizationMap,
     pub auth_error_factory: Option<Arc<dyn AuthErrorFactory + Send + Sync + 'static>>,
+    /// Respond with 405 and `Allow` header if the path is registered under another verb.
+    /// Disabled by default: if several ControllersMiddleware instances are chained, enable it only for the last one.
+    pub method_not_allowed_enabled: bool,
+    pub action_filters: Vec<Arc<dyn ActionFilter + Send + Sync + 'static>>,
+    /// Used to resolve the client ip for ip filters. Claims are checked against the ip reported by the server
+    pub trusted_proxies: TrustedProxies,
 }
 
 impl ControllersMiddleware {
@@ -33,160 +46,418 @@ impl ControllersMiddleware {
         auth_error_factory: Option<Arc<dyn AuthErrorFactory + Send + Sync + 'static>>,
     ) -> Self {
         Self {
-            get: HttpActions::new(),
-            post: HttpActions::new(),
-            put: HttpActions::new(),
-            delete: HttpActions::new(),
+            actions: ArcSwap::from_pointee(ControllersActions::new()),
+            update_lock: Mutex::new(()),
             http_objects: Vec::new(),
             authorization_map: AuthorizationMap::new(authorization),
             auth_error_factory,
+            method_not_allowed_enabled: false,
+            action_filters: Vec::new(),
+            trusted_proxies: TrustedProxies::new(),
         }
     }
 
     pub fn register_get_action<
         TGetAction: GetAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
     >(
-        &mut self,
+        &self,
         action: Arc<TGetAction>,
-    ) {
-        let http_route = HttpRoute::new(action.get_route());
-
-        if let Some(route_keys) = action.get_model_routes() {
-            if let Err(err) = http_route.check_route_keys(&route_keys) {
-                panic!("[GET]: {}", err)
-            }
-        }
-
-        let result = self.get.register(HttpAction {
-            handler: action.clone(),
+    ) -> Result<(), String> {
+        let http_route =
+            HttpRoute::try_new(action.get_route()).map_err(|err| format!("[GET]: {}", err))?;
+        let model_routes = action.get_model_routes();
+        let http_action = create_http_action(&Method::GET, http_route, model_routes, action)?;
 
-            should_be_authorized: if let Some(desc) = action.get_description() {
-                desc.input_params
-                    .check_parameters(&Method::GET, http_route.route.as_str());
-                desc.should_be_authorized
-            } else {
-                ShouldBeAuthorized::UseGlobal
-            },
-            http_route,
-            description: action,
-        });
-
-        if let Err(err) = result {
-            panic!("Failed to register GET action: {}", err);
-        }
+        self.register_http_action(&Method::GET, http_action)
     }
 
     pub fn register_post_action<
         TPostAction: PostAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
     >(
-        &mut self,
+        &self,
         action: Arc<TPostAction>,
-    ) {
-        let http_route = HttpRoute::new(action.get_route());
-        if let Some(route_keys) = action.get_model_routes() {
-            if let Err(err) = http_route.check_route_keys(&route_keys) {
-                panic!("[POST]: {}", err)
-            }
-        }
-
-        let result = self.post.register(HttpAction {
-            handler: action.clone(),
-
-            should_be_authorized: if let Some(desc) = action.get_description() {
-                desc.input_params
-                    .check_parameters(&Method::POST, http_route.route.as_str());
-                desc.should_be_authorized
-            } else {
-                ShouldBeAuthorized::UseGlobal
-            },
-            http_route,
-            description: action,
-        });
+    ) -> Result<(), String> {
+        let http_route =
+            HttpRoute::try_new(action.get_route()).map_err(|err| format!("[POST]: {}", err))?;
+        let model_routes = action.get_model_routes();
+        let http_action = create_http_action(&Method::POST, http_route, model_routes, action)?;
 
-        if let Err(err) = result {
-            panic!("Failed to register POST action: {}", err);
-        }
+        self.register_http_action(&Method::POST, http_action)
     }
 
     pub fn register_put_action<
         TPutAction: PutAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
     >(
-        &mut self,
+        &self,
         action: Arc<TPutAction>,
-    ) {
-        let http_route = HttpRoute::new(action.get_route());
-        if let Some(route_keys) = action.get_model_routes() {
-            if let Err(err) = http_route.check_route_keys(&route_keys) {
-                panic!("[PUT]: {}", err)
-            }
-        }
-
-        let result = self.put.register(HttpAction {
-            handler: action.clone(),
-
-            should_be_authorized: if let Some(desc) = action.get_description() {
-                desc.input_params
-                    .check_parameters(&Method::PUT, http_route.route.as_str());
-                desc.should_be_authorized
-            } else {
-                ShouldBeAuthorized::UseGlobal
-            },
-            http_route,
-            description: action,
-        });
+    ) -> Result<(), String> {
+        let http_route =
+            HttpRoute::try_new(action.get_route()).map_err(|err| format!("[PUT]: {}", err))?;
+        let model_routes = action.get_model_routes();
+        let http_action = create_http_action(&Method::PUT, http_route, model_routes, action)?;
 
-        if let Err(err) = result {
-            panic!("Failed to register PUT action: {}", err);
-        }
+        self.register_http_action(&Method::PUT, http_action)
     }
 
     pub fn register_delete_action<
         TDeleteAction: DeleteAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
     >(
-        &mut self,
+        &self,
         action: Arc<TDeleteAction>,
+    ) -> Result<(), String> {
+        let http_route =
+            HttpRoute::try_new(action.get_route()).map_err(|err| format!("[DELETE]: {}", err))?;
+        let model_routes = action.get_model_routes();
+        let http_action = create_http_action(&Method::DELETE, http_route, model_routes, action)?;
+
+        self.register_http_action(&Method::DELETE, http_action)
+    }
+
+    pub fn register_patch_action<
+        TPatchAction: PatchAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<TPatchAction>,
+    ) -> Result<(), String> {
+        let http_route =
+            HttpRoute::try_new(action.get_route()).map_err(|err| format!("[PATCH]: {}", err))?;
+        let model_routes = action.get_model_routes();
+        let http_action = create_http_action(&Method::PATCH, http_route, model_routes, action)?;
+
+        self.register_http_action(&Method::PATCH, http_action)
+    }
+
+    pub fn register_head_action<
+        THeadAction: HeadAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<THeadAction>,
+    ) -> Result<(), String> {
+        let http_route =
+            HttpRoute::try_new(action.get_route()).map_err(|err| format!("[HEAD]: {}", err))?;
+        let model_routes = action.get_model_routes();
+        let http_action = create_http_action(&Method::HEAD, http_route, model_routes, action)?;
+
+        self.register_http_action(&Method::HEAD, http_action)
+    }
+
+    pub fn register_options_action<
+        TOptionsAction: OptionsAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<TOptionsAction>,
+    ) -> Result<(), String> {
+        let http_route =
+            HttpRoute::try_new(action.get_route()).map_err(|err| format!("[OPTIONS]: {}", err))?;
+        let model_routes = action.get_model_routes();
+        let http_action = create_http_action(&Method::OPTIONS, http_route, model_routes, action)?;
+
+        self.register_http_action(&Method::OPTIONS, http_action)
+    }
+
+    /// Actions can be registered and unregistered at any time. Requests in flight keep the tables they started with.
+    pub fn register_http_action(
+        &self,
+        method: &Method,
+        http_action: HttpAction,
+    ) -> Result<(), String> {
+        self.update_actions(|actions| actions.register(method, http_action))
+    }
+
+    pub fn unregister_action(&self, method: &Method, route: &str) -> Result<(), String> {
+        self.update_actions(|actions| actions.unregister(method, route))
+    }
+
+    /// Unlike unregister - the action keeps its filters, policies and other settings
+    pub fn set_action_enabled(
+        &self,
+        method: &Method,
+        route: &str,
+        enabled: bool,
+    ) -> Result<(), String> {
+        self.update_actions(|actions| actions.set_enabled(method, route, enabled))
+    }
+
+    /// Applies several changes at once: actions are copied and swapped only once.
+    /// Use it to register many actions on startup. If update fails - none of its changes are applied.
+    pub fn update_actions<TResult>(
+        &self,
+        update: impl FnOnce(&mut ControllersActions) -> Result<TResult, String>,
+    ) -> Result<TResult, String> {
+        let _lock = self.update_lock.lock().unwrap();
+
+        let mut actions = ControllersActions::clone(&self.actions.load());
+        let result = update(&mut actions)?;
+        self.actions.store(Arc::new(actions));
+
+        Ok(result)
+    }
+
+    /// Current snapshot of registered actions
+    pub fn get_actions(&self) -> Arc<ControllersActions> {
+        self.actions.load_full()
+    }
+
+    pub fn add_global_action_filter(
+        &mut self,
+        filter: Arc<dyn ActionFilter + Send + Sync + 'static>,
     ) {
-        let http_route = HttpRoute::new(action.get_route());
+        self.action_filters.push(filter);
+    }
 
-        if let Some(route_keys) = action.get_model_routes() {
-            if let Err(err) = http_route.check_route_keys(&route_keys) {
-                panic!("[DELETE]: {}", err)
+    pub fn add_action_filter(
+        &self,
+        method: &Method,
+        route: &str,
+        filter: Arc<dyn ActionFilter + Send + Sync + 'static>,
+    ) -> Result<(), String> {
+        self.update_actions(|actions| {
+            match actions
+                .get_http_actions_mut(method)?
+                .find_action_by_route_mut(route)
+            {
+                Some(http_action) => {
+                    http_action.filters.push(filter);
+                    Ok(())
+                }
+                None => Err(format!("[{}]: Route {} is not registered", method, route)),
             }
-        }
+        })
+    }
 
-        let result = self.delete.register(HttpAction {
-            handler: action.clone(),
+    pub fn add_authorization_policy(
+        &self,
+        method: &Method,
+        route: &str,
+        policy: Arc<dyn AuthorizationPolicy + Send + Sync + 'static>,
+    ) -> Result<(), String> {
+        self.update_actions(|actions| {
+            match actions
+                .get_http_actions_mut(method)?
+                .find_action_by_route_mut(route)
+            {
+                Some(http_action) => {
+                    http_action.policies.push(policy);
+                    Ok(())
+                }
+                None => Err(format!("[{}]: Route {} is not registered", method, route)),
+            }
+        })
+    }
 
-            should_be_authorized: if let Some(desc) = action.get_description() {
-                desc.input_params
-                    .check_parameters(&Method::DELETE, http_route.route.as_str());
-                desc.should_be_authorized
-            } else {
-                ShouldBeAuthorized::UseGlobal
-            },
-            http_route,
-            description: action,
-        });
+    /// Permissions are resolved against roles carried by the request claims. See [`RbacMap`]
+    pub fn set_required_permissions(
+        &self,
+        method: &Method,
+        route: &str,
+        permissions: &[&str],
+    ) -> Result<(), String> {
+        self.update_actions(|actions| {
+            match actions
+                .get_http_actions_mut(method)?
+                .find_action_by_route_mut(route)
+            {
+                Some(http_action) => {
+                    http_action.required_permissions =
+                        permissions.iter().map(|itm| itm.to_string()).collect();
+                    Ok(())
+                }
+                None => Err(format!("[{}]: Route {} is not registered", method, route)),
+            }
+        })
+    }
 
-        if let Err(err) = result {
-            panic!("Failed to register DELETE action: {}", err);
-        }
+    /// Declares media type of the result with the http code, like `image/png` for a binary download.
+    /// By default it is resolved by the result data type.
+    pub fn set_result_content_type(
+        &self,
+        method: &Method,
+        route: &str,
+        http_code: u16,
+        content_type: &str,
+    ) -> Result<(), String> {
+        self.update_actions(|actions| {
+            match actions
+                .get_http_actions_mut(method)?
+                .find_action_by_route_mut(route)
+            {
+                Some(http_action) => {
+                    http_action
+                        .result_content_types
+                        .insert(http_code, content_type.to_string());
+                    Ok(())
+                }
+                None => Err(format!("[{}]: Route {} is not registered", method, route)),
+            }
+        })
+    }
+
+    /// Ip filter is checked before authorization. Works for anonymous actions as well.
+    pub fn set_ip_filter(
+        &self,
+        method: &Method,
+        route: &str,
+        ip_filter: IpFilter,
+    ) -> Result<(), String> {
+        self.update_actions(|actions| {
+            match actions
+                .get_http_actions_mut(method)?
+                .find_action_by_route_mut(route)
+            {
+                Some(http_action) => {
+                    http_action.ip_filter = Some(ip_filter);
+                    Ok(())
+                }
+                None => Err(format!("[{}]: Route {} is not registered", method, route)),
+            }
+        })
+    }
+
+    /// Restricts the action to the listed authentication schemes. By default an action accepts all of them.
+    /// Scheme of the caller is reported by `authorization_map.scheme_resolver`.
+    pub fn set_authentication_schemes(
+        &self,
+        method: &Method,
+        route: &str,
+        schemes: &[&str],
+    ) -> Result<(), String> {
+        let schemes: Vec<String> = schemes.iter().map(|itm| itm.to_string()).collect();
+        self.authorization_map.check_schemes(&schemes)?;
+
+        self.update_actions(|actions| {
+            match actions
+                .get_http_actions_mut(method)?
+                .find_action_by_route_mut(route)
+            {
+                Some(http_action) => {
+                    http_action.authentication_schemes = Some(schemes);
+                    Ok(())
+                }
+                None => Err(format!("[{}]: Route {} is not registered", method, route)),
+            }
+        })
+    }
+
+    pub fn create_route_group(&self, prefix: &str) -> RouteGroup {
+        RouteGroup::new(self, prefix)
+    }
+
+    pub fn list_of_get_route_actions(&self) -> Vec<Arc<HttpAction>> {
+        self.actions.load().get.get_actions().clone()
+    }
+
+    pub fn list_of_post_route_actions(&self) -> Vec<Arc<HttpAction>> {
+        self.actions.load().post.get_actions().clone()
+    }
+
+    pub fn list_of_put_route_actions(&self) -> Vec<Arc<HttpAction>> {
+        self.actions.load().put.get_actions().clone()
+    }
+
+    pub fn list_of_delete_route_actions(&self) -> Vec<Arc<HttpAction>> {
+        self.actions.load().delete.get_actions().clone()
     }
 
-    pub fn list_of_get_route_actions(&self) -> &Vec<HttpAction> {
-        self.get.get_actions()
+    pub fn list_of_patch_route_actions(&self) -> Vec<Arc<HttpAction>> {
+        self.actions.load().patch.get_actions().clone()
     }
 
-    pub fn list_of_post_route_actions(&self) -> &Vec<HttpAction> {
-        self.post.get_actions()
+    pub fn list_of_head_route_actions(&self) -> Vec<Arc<HttpAction>> {
+        self.actions.load().head.get_actions().clone()
     }
 
-    pub fn list_of_put_route_actions(&self) -> &Vec<HttpAction> {
-        self.put.get_actions()
+    pub fn list_of_options_route_actions(&self) -> Vec<Arc<HttpAction>> {
+        self.actions.load().options.get_actions().clone()
     }
 
-    pub fn list_of_delete_route_actions<'s>(&self) -> &Vec<HttpAction> {
-        self.delete.get_actions()
+    pub fn get_allowed_methods(&self, path: &HttpPath) -> Vec<Method> {
+        self.actions.load().get_allowed_methods(path)
+    }
+
+    async fn handle_head_request(
+        &self,
+        actions: &ControllersActions,
+        ctx: &mut HttpContext,
+    ) -> Option<Result<HttpOkResult, HttpFailResult>> {
+        if let Some(result) = self.handle_action(&actions.head, ctx).await {
+            return Some(result);
+        }
+
+        match self.handle_action(&actions.get, ctx).await? {
+            Ok(ok_result) => Some(strip_body(ok_result).await),
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    async fn handle_options_request(
+        &self,
+        actions: &ControllersActions,
+        ctx: &mut HttpContext,
+    ) -> Option<Result<HttpOkResult, HttpFailResult>> {
+        if let Some(result) = self.handle_action(&actions.options, ctx).await {
+            return Some(result);
+        }
+
+        let allowed_methods = actions.get_allowed_methods(&ctx.request.http_path);
+
+        if allowed_methods.len() == 0 {
+            return None;
+        }
+
+        let output = HttpOutput::Content {
+            headers: Some(compile_allow_header(&allowed_methods)),
+            content_type: None,
+            content: vec![],
+        };
+
+        Some(output.into_ok_result(false))
+    }
+
+    /// None - request is not handled by controllers and goes to the next middleware
+    async fn handle_controllers_request(
+        &self,
+        ctx: &mut HttpContext,
+    ) -> Option<Result<HttpOkResult, HttpFailResult>> {
+        let actions = self.actions.load_full();
+
+        let result = match ctx.request.get_method() {
+            &Method::HEAD => self.handle_head_request(&actions, ctx).await,
+            &Method::OPTIONS => self.handle_options_request(&actions, ctx).await,
+            method => match actions.get_http_actions(method) {
+                Some(http_actions) => self.handle_action(http_actions, ctx).await,
+                None => None,
+            },
+        };
+
+        if result.is_some() {
+            return result;
+        }
+
+        if self.method_not_allowed_enabled {
+            let allowed_methods = actions.get_allowed_methods(&ctx.request.http_path);
+
+            if allowed_methods.len() > 0 {
+                return Some(compile_method_not_allowed(&allowed_methods));
+            }
+        }
+
+        None
+    }
+
+    async fn handle_action(
+        &self,
+        http_actions: &HttpActions,
+        ctx: &mut HttpContext,
+    ) -> Option<Result<HttpOkResult, HttpFailResult>> {
+        http_actions
+            .handle_request(
+                ctx,
+                &self.authorization_map,
+                &self.auth_error_factory,
+                &self.action_filters,
+                &self.trusted_proxies,
+            )
+            .await
     }
 }
 
@@ -197,55 +468,416 @@ impl HttpServerMiddleware for ControllersMiddleware {
         ctx: &mut HttpContext,
         get_next: &mut HttpServerRequestFlow,
     ) -> Result<HttpOkResult, HttpFailResult> {
-        match ctx.request.get_method() {
-            &Method::GET => {
-                {
-                    if let Some(result) = self
-                        .get
-                        .handle_request(ctx, &self.authorization_map, &self.auth_error_factory)
-                        .await
-                    {
-                        return result;
-                    }
-                }
-                return get_next.next(ctx).await;
-            }
-            &Method::POST => {
-                if let Some(result) = self
-                    .post
-                    .handle_request(ctx, &self.authorization_map, &self.auth_error_factory)
-                    .await
-                {
-                    return result;
-                } else {
-                    return get_next.next(ctx).await;
-                }
+        if let Some(result) = self.handle_controllers_request(ctx).await {
+            return result;
+        }
+
+        get_next.next(ctx).await
+    }
+}
+
+pub fn create_http_action<THandler: HandleHttpRequest + GetDescription + Send + Sync + 'static>(
+    method: &Method,
+    http_route: HttpRoute,
+    model_routes: Option<Vec<&'static str>>,
+    action: Arc<THandler>,
+) -> Result<HttpAction, String> {
+    if let Some(route_keys) = model_routes {
+        http_route
+            .check_route_keys(&route_keys)
+            .map_err(|err| format!("[{}]: {}", method, err))?;
+    }
+
+    let should_be_authorized = if let Some(desc) = action.get_description() {
+        desc.input_params
+            .check_parameters(method, http_route.route.as_str())?;
+        desc.should_be_authorized
+    } else {
+        ShouldBeAuthorized::UseGlobal
+    };
+
+    Ok(HttpAction {
+        handler: action.clone(),
+        should_be_authorized,
+        http_route,
+        description: action,
+        action_type: std::any::type_name::<THandler>(),
+        swagger_tag: None,
+        filters: Vec::new(),
+        authentication_schemes: None,
+        policies: Vec::new(),
+        required_permissions: Vec::new(),
+        ip_filter: None,
+        result_content_types: HashMap::new(),
+        enabled: true,
+    })
+}
+
+/// Response to HEAD request has the headers of GET response, Content-Length included, but no body
+async fn strip_body(ok_result: HttpOkResult) -> Result<HttpOkResult, HttpFailResult> {
+    let write_telemetry = ok_result.write_telemetry;
+    let response: hyper::Response<hyper::Body> = ok_result.into();
+    let (mut parts, body) = response.into_parts();
+
+    let content_length = match hyper::body::HttpBody::size_hint(&body).exact() {
+        Some(content_length) => content_length,
+        None => hyper::body::to_bytes(body)
+            .await
+            .map(|content| content.len() as u64)
+            .unwrap_or(0),
+    };
+
+    parts
+        .headers
+        .insert(hyper::header::CONTENT_LENGTH, content_length.into());
+
+    let output = HttpOutput::Raw(hyper::Response::from_parts(parts, hyper::Body::empty()));
+    output.into_ok_result(write_telemetry)
+}
+
+fn compile_method_not_allowed(allowed_methods: &[Method]) -> Result<HttpOkResult, HttpFailResult> {
+    let mut builder = hyper::Response::builder().status(405);
+
+    for (header_name, header_value) in compile_allow_header(allowed_methods) {
+        builder = builder.header(header_name, header_value);
+    }
+
+    let output = HttpOutput::Raw(builder.body(hyper::Body::empty()).unwrap());
+
+    output.into_ok_result(false)
+}
+
+fn compile_allow_header(allowed_methods: &[Method]) -> HashMap<String, String> {
+    let mut allow = String::new();
+
+    for method in allowed_methods {
+        if allow.len() > 0 {
+            allow.push_str(", ");
+        }
+
+        allow.push_str(method.as_str());
+    }
+
+    let mut headers = HashMap::new();
+    headers.insert("Allow".to_string(), allow);
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controllers::{
+        documentation::HttpActionDescription,
+        test_utils::{create_http_context, register_test_action, TestHandler},
+    };
+
+    fn create_controllers() -> ControllersMiddleware {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        register_test_action(
+            &controllers,
+            Method::GET,
+            "/api/items",
+            Arc::new(TestHandler::new("items")),
+        )
+        .unwrap();
+
+        register_test_action(
+            &controllers,
+            Method::DELETE,
+            "/api/items",
+            Arc::new(TestHandler::new("deleted")),
+        )
+        .unwrap();
+
+        controllers
+    }
+
+    fn unwrap_raw_response(
+        result: Option<Result<HttpOkResult, HttpFailResult>>,
+    ) -> hyper::Response<hyper::Body> {
+        match result.unwrap().ok().unwrap().output {
+            HttpOutput::Raw(response) => response,
+            _ => panic!("Raw output is expected"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_method_not_allowed_is_disabled_by_default() {
+        let controllers = create_controllers();
+
+        let mut ctx = create_http_context(Method::POST, "/api/items", "127.0.0.1", &[]);
+
+        assert!(controllers
+            .handle_controllers_request(&mut ctx)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_method_not_allowed_with_allow_header() {
+        let mut controllers = create_controllers();
+        controllers.method_not_allowed_enabled = true;
+
+        let mut ctx = create_http_context(Method::POST, "/api/items", "127.0.0.1", &[]);
+        let response = unwrap_raw_response(controllers.handle_controllers_request(&mut ctx).await);
+
+        assert_eq!(405, response.status().as_u16());
+        assert_eq!(
+            "GET, DELETE, HEAD, OPTIONS",
+            response.headers().get("Allow").unwrap().to_str().unwrap()
+        );
+
+        // Path which is not registered goes to the next middleware
+        let mut ctx = create_http_context(Method::POST, "/api/unknown", "127.0.0.1", &[]);
+        assert!(controllers
+            .handle_controllers_request(&mut ctx)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_head_falls_back_to_get_without_body() {
+        let controllers = create_controllers();
+
+        let mut ctx = create_http_context(Method::HEAD, "/api/items", "127.0.0.1", &[]);
+        let response = unwrap_raw_response(controllers.handle_controllers_request(&mut ctx).await);
+
+        assert_eq!(200, response.status().as_u16());
+        assert_eq!(
+            "5",
+            response
+                .headers()
+                .get(hyper::header::CONTENT_LENGTH)
+                .unwrap()
+                .to_str()
+                .unwrap()
+        );
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(0, body.len());
+    }
+
+    #[tokio::test]
+    async fn test_options_answers_with_allow_header() {
+        let controllers = create_controllers();
+
+        let mut ctx = create_http_context(Method::OPTIONS, "/api/items", "127.0.0.1", &[]);
+        let result = controllers
+            .handle_controllers_request(&mut ctx)
+            .await
+            .unwrap()
+            .ok()
+            .unwrap();
+
+        match result.output {
+            HttpOutput::Content {
+                headers, content, ..
+            } => {
+                assert_eq!(
+                    "GET, DELETE, HEAD, OPTIONS",
+                    headers.unwrap().get("Allow").unwrap()
+                );
+                assert_eq!(0, content.len());
             }
-            &Method::PUT => {
-                if let Some(result) = self
-                    .put
-                    .handle_request(ctx, &self.authorization_map, &self.auth_error_factory)
-                    .await
-                {
-                    return result;
-                } else {
-                    return get_next.next(ctx).await;
-                }
+            _ => panic!("Content output is expected"),
+        }
+
+        let mut ctx = create_http_context(Method::OPTIONS, "/api/unknown", "127.0.0.1", &[]);
+        assert!(controllers
+            .handle_controllers_request(&mut ctx)
+            .await
+            .is_none());
+    }
+
+    struct BlockingHandler {
+        started: tokio::sync::Notify,
+        release: tokio::sync::Notify,
+    }
+
+    #[async_trait]
+    impl HandleHttpRequest for BlockingHandler {
+        async fn handle_request(
+            &self,
+            _http_route: &HttpRoute,
+            _ctx: &mut HttpContext,
+        ) -> Result<HttpOkResult, HttpFailResult> {
+            self.started.notify_one();
+            self.release.notified().await;
+
+            HttpOutput::Content {
+                headers: None,
+                content_type: None,
+                content: Vec::new(),
             }
-            &Method::DELETE => {
-                if let Some(result) = self
-                    .delete
-                    .handle_request(ctx, &self.authorization_map, &self.auth_error_factory)
-                    .await
-                {
-                    return result;
-                } else {
-                    return get_next.next(ctx).await;
+            .into_ok_result(false)
+        }
+    }
+
+    impl GetDescription for BlockingHandler {
+        fn get_description(&self) -> Option<HttpActionDescription> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_and_unregister_at_runtime() {
+        let controllers = create_controllers();
+
+        register_test_action(
+            &controllers,
+            Method::GET,
+            "/api/orders",
+            Arc::new(TestHandler::new("orders")),
+        )
+        .unwrap();
+
+        let mut ctx = create_http_context(Method::GET, "/api/orders", "127.0.0.1", &[]);
+        assert!(controllers
+            .handle_controllers_request(&mut ctx)
+            .await
+            .unwrap()
+            .is_ok());
+
+        controllers
+            .unregister_action(&Method::GET, "/api/orders")
+            .unwrap();
+        assert!(controllers
+            .unregister_action(&Method::GET, "/api/orders")
+            .is_err());
+
+        let mut ctx = create_http_context(Method::GET, "/api/orders", "127.0.0.1", &[]);
+        assert!(controllers
+            .handle_controllers_request(&mut ctx)
+            .await
+            .is_none());
+
+        // Other actions are not affected
+        let mut ctx = create_http_context(Method::GET, "/api/items", "127.0.0.1", &[]);
+        assert!(controllers
+            .handle_controllers_request(&mut ctx)
+            .await
+            .unwrap()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_in_flight_keeps_its_snapshot() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        let handler = Arc::new(BlockingHandler {
+            started: tokio::sync::Notify::new(),
+            release: tokio::sync::Notify::new(),
+        });
+
+        let http_action = create_http_action(
+            &Method::GET,
+            HttpRoute::new("/api/slow"),
+            None,
+            handler.clone(),
+        )
+        .unwrap();
+        controllers
+            .register_http_action(&Method::GET, http_action)
+            .unwrap();
+
+        let mut ctx = create_http_context(Method::GET, "/api/slow", "127.0.0.1", &[]);
+
+        let (result, _) = tokio::join!(controllers.handle_controllers_request(&mut ctx), async {
+            handler.started.notified().await;
+            controllers
+                .unregister_action(&Method::GET, "/api/slow")
+                .unwrap();
+            handler.release.notify_one();
+        });
+
+        assert!(result.unwrap().is_ok());
+
+        let mut ctx = create_http_context(Method::GET, "/api/slow", "127.0.0.1", &[]);
+        assert!(controllers
+            .handle_controllers_request(&mut ctx)
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_action_keeps_its_settings() {
+        let mut controllers = create_controllers();
+        controllers.method_not_allowed_enabled = true;
+
+        controllers
+            .set_required_permissions(&Method::DELETE, "/api/items", &["items.delete"])
+            .unwrap();
+        controllers
+            .set_action_enabled(&Method::DELETE, "/api/items", false)
+            .unwrap();
+
+        let path = HttpPath::from_str("/api/items");
+        assert_eq!(
+            vec![Method::GET, Method::HEAD, Method::OPTIONS],
+            controllers.get_allowed_methods(&path)
+        );
+
+        let mut ctx = create_http_context(Method::DELETE, "/api/items", "127.0.0.1", &[]);
+        let response = unwrap_raw_response(controllers.handle_controllers_request(&mut ctx).await);
+        assert_eq!(405, response.status().as_u16());
+
+        // Disabled route is still taken
+        assert!(register_test_action(
+            &controllers,
+            Method::DELETE,
+            "/api/items",
+            Arc::new(TestHandler::new("deleted")),
+        )
+        .is_err());
+
+        controllers
+            .set_action_enabled(&Method::DELETE, "/api/items", true)
+            .unwrap();
+
+        let actions = controllers.list_of_delete_route_actions();
+        assert!(actions[0].enabled);
+        assert_eq!(
+            vec!["items.delete".to_string()],
+            actions[0].required_permissions
+        );
+
+        assert!(controllers
+            .set_action_enabled(&Method::DELETE, "/api/unknown", false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_batch_update_is_applied_at_once() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        controllers
+            .update_actions(|actions| {
+                for route in ["/api/orders", "/api/invoices"] {
+                    let handler = Arc::new(TestHandler::new("ok"));
+                    let http_action =
+                        create_http_action(&Method::GET, HttpRoute::new(route), None, handler)?;
+                    actions.register(&Method::GET, http_action)?;
                 }
+
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(2, controllers.list_of_get_route_actions().len());
+
+        let result = controllers.update_actions(|actions| {
+            for route in ["/api/payments", "/api/orders"] {
+                let handler = Arc::new(TestHandler::new("ok"));
+                let http_action =
+                    create_http_action(&Method::GET, HttpRoute::new(route), None, handler)?;
+                actions.register(&Method::GET, http_action)?;
             }
-            _ => {
-                return get_next.next(ctx).await;
-            }
-        }
+
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(2, controllers.list_of_get_route_actions().len());
     }
 }
@@ -1,16 +1,42 @@
 pub mod actions;
 
+mod action_filter;
+mod api_key_location;
 mod auth_error_factory;
+mod authentication_scheme_resolver;
 mod authorization;
+mod authorization_denial;
 mod authorization_map;
+mod authorization_policy;
+mod authorization_provider;
+mod claims_expression;
+mod controllers_actions;
 mod http_route;
+mod ip_filter;
 mod middleware;
+mod oauth2_settings;
+mod rbac;
 mod required_claims;
+mod route_group;
+#[cfg(test)]
+pub(crate) mod test_utils;
 pub use middleware::ControllersMiddleware;
 pub mod documentation;
 
+pub use action_filter::*;
+pub use api_key_location::*;
 pub use auth_error_factory::*;
+pub use authentication_scheme_resolver::*;
 pub use authorization::*;
+pub use authorization_denial::*;
 pub use authorization_map::*;
+pub use authorization_policy::*;
+pub use authorization_provider::*;
+pub use claims_expression::*;
+pub use controllers_actions::*;
 pub use http_route::*;
+pub use ip_filter::*;
+pub use oauth2_settings::*;
+pub use rbac::*;
 pub use required_claims::*;
+pub use route_group::*;
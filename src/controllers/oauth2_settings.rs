@@ -0,0 +1,89 @@
+#[derive(Debug, Clone)]
+pub struct OAuth2Scope {
+    pub name: String,
+    pub description: String,
+}
+
+/// OAuth2 flows swagger UI can run against the identity provider.
+/// Authorization code flow is enabled if authorization url is set.
+#[derive(Debug, Clone)]
+pub struct OAuth2Settings {
+    pub scheme_name: String,
+    pub authorization_url: Option<String>,
+    pub token_url: String,
+    pub refresh_url: Option<String>,
+    pub client_credentials: bool,
+    pub scopes: Vec<OAuth2Scope>,
+}
+
+impl OAuth2Settings {
+    pub const DEFAULT_SCHEME_NAME: &'static str = "OAuth2";
+
+    pub fn authorization_code(authorization_url: &str, token_url: &str) -> Self {
+        Self {
+            scheme_name: Self::DEFAULT_SCHEME_NAME.to_string(),
+            authorization_url: Some(authorization_url.to_string()),
+            token_url: token_url.to_string(),
+            refresh_url: None,
+            client_credentials: false,
+            scopes: Vec::new(),
+        }
+    }
+
+    pub fn client_credentials(token_url: &str) -> Self {
+        Self {
+            scheme_name: Self::DEFAULT_SCHEME_NAME.to_string(),
+            authorization_url: None,
+            token_url: token_url.to_string(),
+            refresh_url: None,
+            client_credentials: true,
+            scopes: Vec::new(),
+        }
+    }
+
+    pub fn with_client_credentials(mut self) -> Self {
+        self.client_credentials = true;
+        self
+    }
+
+    pub fn with_refresh_url(mut self, refresh_url: &str) -> Self {
+        self.refresh_url = Some(refresh_url.to_string());
+        self
+    }
+
+    pub fn with_scope(mut self, name: &str, description: &str) -> Self {
+        self.scopes.push(OAuth2Scope {
+            name: name.to_string(),
+            description: description.to_string(),
+        });
+        self
+    }
+
+    pub fn with_scheme_name(mut self, scheme_name: &str) -> Self {
+        self.scheme_name = scheme_name.to_string();
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenIdConnectSettings {
+    pub scheme_name: String,
+    pub openid_connect_url: String,
+}
+
+impl OpenIdConnectSettings {
+    pub const DEFAULT_SCHEME_NAME: &'static str = "OpenIdConnect";
+
+    /// Url of the discovery document: https://{issuer}/.well-known/openid-configuration
+    pub fn new(openid_connect_url: &str) -> Self {
+        Self {
+            scheme_name: Self::DEFAULT_SCHEME_NAME.to_string(),
+            openid_connect_url: openid_connect_url.to_string(),
+        }
+    }
+
+    pub fn with_scheme_name(mut self, scheme_name: &str) -> Self {
+        self.scheme_name = scheme_name.to_string();
+        self
+    }
+}
@@ -0,0 +1,4 @@
+mod rbac_map;
+mod role_definition;
+pub use rbac_map::*;
+pub use role_definition::*;
@@ -0,0 +1,214 @@
+use std::collections::{HashMap, HashSet};
+
+use my_http_server::RequestClaim;
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use super::RoleDefinition;
+use crate::controllers::AuthorizationDenialReason;
+
+/// Role definitions. Roles are carried by the request as claims with the role name as claim id.
+pub struct RbacMap {
+    roles: HashMap<String, RoleDefinition>,
+}
+
+impl RbacMap {
+    pub fn new() -> Self {
+        Self {
+            roles: HashMap::new(),
+        }
+    }
+
+    pub fn add_role(&mut self, role: RoleDefinition) -> Result<(), String> {
+        if self.roles.contains_key(&role.name) {
+            return Err(format!("Role {} is already registered", role.name));
+        }
+
+        for included in &role.includes {
+            if included == &role.name {
+                return Err(format!("Role {} can not include itself", role.name));
+            }
+
+            if !self.roles.contains_key(included) {
+                return Err(format!(
+                    "Role {} includes role {} which is not registered",
+                    role.name, included
+                ));
+            }
+        }
+
+        self.roles.insert(role.name.to_string(), role);
+        Ok(())
+    }
+
+    pub fn get_role(&self, name: &str) -> Option<&RoleDefinition> {
+        self.roles.get(name)
+    }
+
+    pub fn has_roles(&self) -> bool {
+        self.roles.len() > 0
+    }
+
+    /// Permissions of the role including permissions of all the roles it includes
+    pub fn get_role_permissions(&self, role: &str) -> HashSet<&str> {
+        let mut result = HashSet::new();
+        let mut visited = HashSet::new();
+        self.populate_permissions(role, &mut visited, &mut result);
+        result
+    }
+
+    pub fn role_has_permission(&self, role: &str, permission: &str) -> bool {
+        self.get_role_permissions(role).contains(permission)
+    }
+
+    /// Roles which grant the permission directly or through included roles
+    pub fn get_roles_with_permission(&self, permission: &str) -> Vec<&str> {
+        let mut result: Vec<&str> = self
+            .roles
+            .keys()
+            .filter(|role| self.role_has_permission(role, permission))
+            .map(|role| role.as_str())
+            .collect();
+
+        result.sort();
+        result
+    }
+
+    pub fn check_permissions(
+        &self,
+        now: &DateTimeAsMicroseconds,
+        request_ip: &str,
+        request_claims: &[RequestClaim],
+        required_permissions: &[String],
+    ) -> Result<(), AuthorizationDenialReason> {
+        let mut granted = HashSet::new();
+
+        for claim in request_claims {
+            if claim.expires.unix_microseconds < now.unix_microseconds {
+                continue;
+            }
+
+            if !claim.is_ip_allowed(request_ip) {
+                continue;
+            }
+
+            granted.extend(self.get_role_permissions(claim.id));
+        }
+
+        for permission in required_permissions {
+            if !granted.contains(permission.as_str()) {
+                return Err(AuthorizationDenialReason::PermissionIsMissing(
+                    permission.to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn populate_permissions<'s>(
+        &'s self,
+        role: &str,
+        visited: &mut HashSet<String>,
+        result: &mut HashSet<&'s str>,
+    ) {
+        if !visited.insert(role.to_string()) {
+            return;
+        }
+
+        let Some(role) = self.roles.get(role) else {
+            return;
+        };
+
+        for permission in &role.permissions {
+            result.insert(permission.as_str());
+        }
+
+        for included in &role.includes {
+            self.populate_permissions(included, visited, result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_rbac_map() -> RbacMap {
+        let mut result = RbacMap::new();
+
+        result
+            .add_role(RoleDefinition::new("viewer").with_permissions(&["accounts.read"]))
+            .unwrap();
+
+        result
+            .add_role(
+                RoleDefinition::new("operator")
+                    .with_permissions(&["accounts.write"])
+                    .with_included_roles(&["viewer"]),
+            )
+            .unwrap();
+
+        result
+            .add_role(
+                RoleDefinition::new("admin")
+                    .with_permissions(&["users.manage"])
+                    .with_included_roles(&["operator"]),
+            )
+            .unwrap();
+
+        result
+    }
+
+    fn create_claim(id: &str) -> RequestClaim {
+        RequestClaim {
+            id,
+            expires: DateTimeAsMicroseconds::new(
+                DateTimeAsMicroseconds::now().unix_microseconds + 60_000_000,
+            ),
+            allowed_ips: None,
+        }
+    }
+
+    #[test]
+    fn test_role_hierarchy() {
+        let rbac_map = create_rbac_map();
+
+        assert!(rbac_map.role_has_permission("admin", "accounts.read"));
+        assert!(rbac_map.role_has_permission("operator", "accounts.read"));
+        assert!(!rbac_map.role_has_permission("viewer", "accounts.write"));
+
+        assert_eq!(
+            rbac_map.get_roles_with_permission("accounts.write"),
+            vec!["admin", "operator"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_roles() {
+        let mut rbac_map = create_rbac_map();
+
+        assert!(rbac_map.add_role(RoleDefinition::new("viewer")).is_err());
+        assert!(rbac_map
+            .add_role(RoleDefinition::new("support").with_included_roles(&["unknown"]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_permissions() {
+        let rbac_map = create_rbac_map();
+        let now = DateTimeAsMicroseconds::now();
+
+        let required = vec!["accounts.write".to_string()];
+
+        assert!(rbac_map
+            .check_permissions(&now, "127.0.0.1", &[create_claim("admin")], &required)
+            .is_ok());
+
+        assert_eq!(
+            rbac_map.check_permissions(&now, "127.0.0.1", &[create_claim("viewer")], &required),
+            Err(AuthorizationDenialReason::PermissionIsMissing(
+                "accounts.write".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,29 @@
+/// Role with its own permissions. Included roles give their permissions to this role as well.
+#[derive(Debug, Clone)]
+pub struct RoleDefinition {
+    pub name: String,
+    pub permissions: Vec<String>,
+    pub includes: Vec<String>,
+}
+
+impl RoleDefinition {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            permissions: Vec::new(),
+            includes: Vec::new(),
+        }
+    }
+
+    pub fn with_permissions(mut self, permissions: &[&str]) -> Self {
+        self.permissions
+            .extend(permissions.iter().map(|itm| itm.to_string()));
+        self
+    }
+
+    pub fn with_included_roles(mut self, roles: &[&str]) -> Self {
+        self.includes
+            .extend(roles.iter().map(|itm| itm.to_string()));
+        self
+    }
+}
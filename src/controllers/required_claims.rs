@@ -1,20 +1,26 @@
 use my_http_server::RequestClaim;
 
+use rust_extensions::date_time::DateTimeAsMicroseconds;
+
+use super::{AuthorizationDenialReason, ClaimsExpression};
+
 #[derive(Debug, Clone)]
 pub struct RequiredClaims {
-    required_claims: Vec<String>,
+    expression: ClaimsExpression,
 }
 
 impl RequiredClaims {
     pub fn no_claims() -> Self {
         Self {
-            required_claims: vec![],
+            expression: ClaimsExpression::AllOf(vec![]),
         }
     }
 
     pub fn from_vec(claims: Vec<String>) -> Self {
         Self {
-            required_claims: claims,
+            expression: ClaimsExpression::AllOf(
+                claims.into_iter().map(ClaimsExpression::Claim).collect(),
+            ),
         }
     }
 
@@ -22,39 +28,47 @@ impl RequiredClaims {
         let mut required_claims = Vec::with_capacity(claims.len());
 
         for claim in claims {
-            required_claims.push(claim.to_string());
+            required_claims.push(ClaimsExpression::claim(claim));
+        }
+
+        Self {
+            expression: ClaimsExpression::AllOf(required_claims),
         }
+    }
+
+    pub fn from_expression(expression: ClaimsExpression) -> Self {
+        Self { expression }
+    }
 
-        Self { required_claims }
+    /// Parses expression like `admin | (support & read-only)`
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let expression = ClaimsExpression::parse(src)?;
+        Ok(Self { expression })
+    }
+
+    pub fn get_expression(&self) -> &ClaimsExpression {
+        &self.expression
+    }
+
+    pub fn has_claims(&self) -> bool {
+        !self.expression.is_empty()
     }
 
     pub fn authorized_by_claims(
         &self,
         request_ip: &str,
         request_claims: Option<Vec<RequestClaim>>,
-    ) -> Option<String> {
+    ) -> Result<(), AuthorizationDenialReason> {
         // No Claims means - we are authorized
-        if self.required_claims.len() == 0 {
-            return None;
+        if !self.has_claims() {
+            return Ok(());
         }
 
-        if let Some(request_claims) = request_claims {
-            for required_claim in &self.required_claims {
-                let request_claim = request_claims.iter().find(|c| c.id == required_claim);
+        let now = DateTimeAsMicroseconds::now();
 
-                let Some(request_claim) = request_claim else {
-                    return Some(required_claim.to_owned());
-                };
-
-                if !request_claim.is_ip_allowed(request_ip) {
-                    return Some(request_claim.id.to_string());
-                }
-            }
-
-            return None;
+        match request_claims {
+            Some(request_claims) => self.expression.check(&now, request_ip, &request_claims),
+            None => self.expression.check(&now, request_ip, &[]),
         }
-
-        let first_claim = &self.required_claims[0];
-        Some(first_claim.to_string())
     }
 }
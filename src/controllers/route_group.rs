@@ -0,0 +1,360 @@
+use std::sync::Arc;
+
+use hyper::Method;
+
+use super::{
+    actions::{
+        DeleteAction, GetAction, GetDescription, HandleHttpRequest, HeadAction, OptionsAction,
+        PatchAction, PostAction, PutAction,
+    },
+    documentation::ShouldBeAuthorized,
+    ActionFilter, AuthorizationPolicy, ControllersMiddleware, HttpRoute, IpFilter, RequiredClaims,
+};
+
+/// Registers actions under the common route prefix.
+/// Actions inherit authorization of the group unless they have their own and the group swagger tag.
+pub struct RouteGroup<'s> {
+    controllers: &'s ControllersMiddleware,
+    prefix: String,
+    should_be_authorized: Option<ShouldBeAuthorized>,
+    swagger_tag: Option<String>,
+    filters: Vec<Arc<dyn ActionFilter + Send + Sync + 'static>>,
+    authentication_schemes: Option<Vec<String>>,
+    policies: Vec<Arc<dyn AuthorizationPolicy + Send + Sync + 'static>>,
+    required_permissions: Vec<String>,
+    ip_filter: Option<IpFilter>,
+}
+
+impl<'s> RouteGroup<'s> {
+    /// Prefix is normalized: `api`, `/api` and `/api/` are the same prefix
+    pub fn new(controllers: &'s ControllersMiddleware, prefix: &str) -> Self {
+        let prefix = prefix.trim_matches('/');
+
+        Self {
+            controllers,
+            prefix: if prefix.len() == 0 {
+                String::new()
+            } else {
+                format!("/{}", prefix)
+            },
+            should_be_authorized: None,
+            swagger_tag: None,
+            filters: Vec::new(),
+            authentication_schemes: None,
+            policies: Vec::new(),
+            required_permissions: Vec::new(),
+            ip_filter: None,
+        }
+    }
+
+    pub fn with_should_be_authorized(mut self, should_be_authorized: ShouldBeAuthorized) -> Self {
+        self.should_be_authorized = Some(should_be_authorized);
+        self
+    }
+
+    pub fn with_required_claims(mut self, required_claims: RequiredClaims) -> Self {
+        self.should_be_authorized = Some(ShouldBeAuthorized::YesWithClaims(required_claims));
+        self
+    }
+
+    pub fn with_swagger_tag(mut self, swagger_tag: &str) -> Self {
+        self.swagger_tag = Some(swagger_tag.to_string());
+        self
+    }
+
+    pub fn with_filter(mut self, filter: Arc<dyn ActionFilter + Send + Sync + 'static>) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn with_authentication_schemes(mut self, schemes: &[&str]) -> Self {
+        self.authentication_schemes = Some(schemes.iter().map(|itm| itm.to_string()).collect());
+        self
+    }
+
+    pub fn with_authorization_policy(
+        mut self,
+        policy: Arc<dyn AuthorizationPolicy + Send + Sync + 'static>,
+    ) -> Self {
+        self.policies.push(policy);
+        self
+    }
+
+    pub fn with_required_permissions(mut self, permissions: &[&str]) -> Self {
+        self.required_permissions
+            .extend(permissions.iter().map(|itm| itm.to_string()));
+        self
+    }
+
+    pub fn with_ip_filter(mut self, ip_filter: IpFilter) -> Self {
+        self.ip_filter = Some(ip_filter);
+        self
+    }
+
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn compile_route(&self, route: &str) -> String {
+        let route = route.trim_start_matches('/');
+
+        if route.len() == 0 && self.prefix.len() > 0 {
+            return self.prefix.to_string();
+        }
+
+        format!("{}/{}", self.prefix, route)
+    }
+
+    fn register<THandler: HandleHttpRequest + GetDescription + Send + Sync + 'static>(
+        &self,
+        method: Method,
+        route: &str,
+        model_routes: Option<Vec<&'static str>>,
+        action: Arc<THandler>,
+    ) -> Result<(), String> {
+        let http_route =
+            HttpRoute::try_new(route).map_err(|err| format!("[{}]: {}", method, err))?;
+
+        let mut http_action =
+            super::middleware::create_http_action(&method, http_route, model_routes, action)?;
+
+        if let ShouldBeAuthorized::UseGlobal = &http_action.should_be_authorized {
+            if let Some(should_be_authorized) = &self.should_be_authorized {
+                http_action.should_be_authorized = should_be_authorized.clone();
+            }
+        }
+
+        if self.swagger_tag.is_some() {
+            http_action.swagger_tag = self.swagger_tag.clone();
+        }
+
+        http_action.filters.extend(self.filters.iter().cloned());
+        http_action.policies.extend(self.policies.iter().cloned());
+        http_action
+            .required_permissions
+            .extend(self.required_permissions.iter().cloned());
+
+        if self.ip_filter.is_some() {
+            http_action.ip_filter = self.ip_filter.clone();
+        }
+
+        if let Some(schemes) = &self.authentication_schemes {
+            self.controllers.authorization_map.check_schemes(schemes)?;
+            http_action.authentication_schemes = Some(schemes.clone());
+        }
+
+        self.controllers.register_http_action(&method, http_action)
+    }
+
+    pub fn register_get_action<
+        TGetAction: GetAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<TGetAction>,
+    ) -> Result<(), String> {
+        let route = self.compile_route(action.get_route());
+        let model_routes = action.get_model_routes();
+        self.register(Method::GET, route.as_str(), model_routes, action)
+    }
+
+    pub fn register_post_action<
+        TPostAction: PostAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<TPostAction>,
+    ) -> Result<(), String> {
+        let route = self.compile_route(action.get_route());
+        let model_routes = action.get_model_routes();
+        self.register(Method::POST, route.as_str(), model_routes, action)
+    }
+
+    pub fn register_put_action<
+        TPutAction: PutAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<TPutAction>,
+    ) -> Result<(), String> {
+        let route = self.compile_route(action.get_route());
+        let model_routes = action.get_model_routes();
+        self.register(Method::PUT, route.as_str(), model_routes, action)
+    }
+
+    pub fn register_delete_action<
+        TDeleteAction: DeleteAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<TDeleteAction>,
+    ) -> Result<(), String> {
+        let route = self.compile_route(action.get_route());
+        let model_routes = action.get_model_routes();
+        self.register(Method::DELETE, route.as_str(), model_routes, action)
+    }
+
+    pub fn register_patch_action<
+        TPatchAction: PatchAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<TPatchAction>,
+    ) -> Result<(), String> {
+        let route = self.compile_route(action.get_route());
+        let model_routes = action.get_model_routes();
+        self.register(Method::PATCH, route.as_str(), model_routes, action)
+    }
+
+    pub fn register_head_action<
+        THeadAction: HeadAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<THeadAction>,
+    ) -> Result<(), String> {
+        let route = self.compile_route(action.get_route());
+        let model_routes = action.get_model_routes();
+        self.register(Method::HEAD, route.as_str(), model_routes, action)
+    }
+
+    pub fn register_options_action<
+        TOptionsAction: OptionsAction + HandleHttpRequest + GetDescription + Send + Sync + 'static,
+    >(
+        &self,
+        action: Arc<TOptionsAction>,
+    ) -> Result<(), String> {
+        let route = self.compile_route(action.get_route());
+        let model_routes = action.get_model_routes();
+        self.register(Method::OPTIONS, route.as_str(), model_routes, action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controllers::{
+        documentation::HttpActionDescription,
+        test_utils::{register_test_action, TestHandler},
+    };
+
+    fn get_routes(controllers: &ControllersMiddleware) -> Vec<String> {
+        controllers
+            .list_of_get_route_actions()
+            .iter()
+            .map(|action| action.http_route.route.to_string())
+            .collect()
+    }
+
+    fn anonymous_description() -> HttpActionDescription<'static> {
+        HttpActionDescription {
+            controller_name: "Status",
+            summary: "Status",
+            description: "Returns status",
+            input_params: vec![].into(),
+            results: vec![],
+            should_be_authorized: ShouldBeAuthorized::No,
+        }
+    }
+
+    #[test]
+    fn test_prefix_is_joined_with_single_slash() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        controllers
+            .create_route_group("api")
+            .register_get_action(Arc::new(TestHandler::new("ok").with_route("items")))
+            .unwrap();
+
+        controllers
+            .create_route_group("/api/")
+            .register_get_action(Arc::new(TestHandler::new("ok").with_route("/orders")))
+            .unwrap();
+
+        controllers
+            .create_route_group("/api/v2")
+            .register_get_action(Arc::new(TestHandler::new("ok").with_route("/")))
+            .unwrap();
+
+        controllers
+            .create_route_group("/")
+            .register_get_action(Arc::new(TestHandler::new("ok").with_route("/status")))
+            .unwrap();
+
+        assert_eq!(
+            vec!["/api/items", "/api/orders", "/api/v2", "/status"],
+            get_routes(&controllers)
+        );
+    }
+
+    #[test]
+    fn test_group_settings_are_inherited() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        let group = controllers
+            .create_route_group("/admin")
+            .with_required_claims(RequiredClaims::parse("admin").unwrap())
+            .with_swagger_tag("Admin")
+            .with_required_permissions(&["admin.read"]);
+
+        group
+            .register_get_action(Arc::new(TestHandler::new("ok").with_route("/users")))
+            .unwrap();
+
+        let action = &controllers.list_of_get_route_actions()[0];
+
+        match &action.should_be_authorized {
+            ShouldBeAuthorized::YesWithClaims(claims) => {
+                assert_eq!("admin", claims.get_expression().to_string())
+            }
+            _ => panic!("Claims of the group are expected"),
+        }
+
+        assert_eq!(Some("Admin"), action.swagger_tag.as_deref());
+        assert_eq!(vec!["admin.read".to_string()], action.required_permissions);
+    }
+
+    #[test]
+    fn test_action_settings_override_group_settings() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        controllers
+            .create_route_group("/admin")
+            .with_required_claims(RequiredClaims::parse("admin").unwrap())
+            .with_required_permissions(&["admin.read"])
+            .register_get_action(Arc::new(
+                TestHandler::new("ok")
+                    .with_route("/status")
+                    .with_description(anonymous_description),
+            ))
+            .unwrap();
+
+        // Settings are changed after the action is registered by the group
+        controllers
+            .set_required_permissions(&Method::GET, "/admin/status", &["status.read"])
+            .unwrap();
+
+        let action = &controllers.list_of_get_route_actions()[0];
+
+        assert!(matches!(
+            action.should_be_authorized,
+            ShouldBeAuthorized::No
+        ));
+        assert_eq!(vec!["status.read".to_string()], action.required_permissions);
+
+        // Routes registered outside of the group do not inherit anything
+        register_test_action(
+            &controllers,
+            Method::GET,
+            "/admin/health",
+            Arc::new(TestHandler::new("ok")),
+        )
+        .unwrap();
+
+        let action = controllers
+            .list_of_get_route_actions()
+            .into_iter()
+            .find(|action| action.http_route.route == "/admin/health")
+            .unwrap();
+
+        assert!(matches!(
+            action.should_be_authorized,
+            ShouldBeAuthorized::UseGlobal
+        ));
+        assert_eq!(0, action.required_permissions.len());
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use hyper::Method;
+use my_http_server::{
+    HttpContext, HttpFailResult, HttpOkResult, HttpOutput, HttpRequest, WebContentType,
+};
+
+use super::{
+    actions::{DeleteAction, GetAction, GetDescription, HandleHttpRequest, PostAction},
+    documentation::HttpActionDescription,
+    ControllersMiddleware, HttpRoute,
+};
+
+pub fn create_http_context(
+    method: Method,
+    uri: &str,
+    peer_ip: &str,
+    headers: &[(&str, &str)],
+) -> HttpContext {
+    let mut builder = hyper::Request::builder().method(method).uri(uri);
+
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+
+    let addr: SocketAddr = format!("{}:5123", peer_ip).parse().unwrap();
+
+    HttpContext::new(HttpRequest::new(
+        builder.body(hyper::Body::empty()).unwrap(),
+        addr,
+    ))
+}
+
+/// Handler which answers with the text content and counts the calls
+pub struct TestHandler {
+    pub content: &'static str,
+    pub calls: AtomicUsize,
+    pub description: Option<fn() -> HttpActionDescription<'static>>,
+    pub route: &'static str,
+}
+
+impl TestHandler {
+    pub fn new(content: &'static str) -> Self {
+        Self {
+            content,
+            calls: AtomicUsize::new(0),
+            description: None,
+            route: "/",
+        }
+    }
+
+    pub fn with_route(mut self, route: &'static str) -> Self {
+        self.route = route;
+        self
+    }
+
+    pub fn with_description(mut self, description: fn() -> HttpActionDescription<'static>) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn get_calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait::async_trait]
+impl HandleHttpRequest for TestHandler {
+    async fn handle_request(
+        &self,
+        _http_route: &HttpRoute,
+        _ctx: &mut HttpContext,
+    ) -> Result<HttpOkResult, HttpFailResult> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+
+        HttpOutput::Content {
+            headers: None,
+            content_type: Some(WebContentType::Text),
+            content: self.content.as_bytes().to_vec(),
+        }
+        .into_ok_result(false)
+    }
+}
+
+impl GetDescription for TestHandler {
+    fn get_description(&self) -> Option<HttpActionDescription> {
+        self.description.map(|description| description())
+    }
+}
+
+impl GetAction for TestHandler {
+    fn get_route(&self) -> &str {
+        self.route
+    }
+
+    fn get_model_routes(&self) -> Option<Vec<&'static str>> {
+        None
+    }
+}
+
+impl PostAction for TestHandler {
+    fn get_route(&self) -> &str {
+        self.route
+    }
+
+    fn get_model_routes(&self) -> Option<Vec<&'static str>> {
+        None
+    }
+}
+
+impl DeleteAction for TestHandler {
+    fn get_route(&self) -> &str {
+        self.route
+    }
+
+    fn get_model_routes(&self) -> Option<Vec<&'static str>> {
+        None
+    }
+}
+
+pub fn register_test_action(
+    controllers: &ControllersMiddleware,
+    method: Method,
+    route: &str,
+    handler: Arc<TestHandler>,
+) -> Result<(), String> {
+    let http_route = HttpRoute::try_new(route)?;
+    let http_action = super::middleware::create_http_action(&method, http_route, None, handler)?;
+    controllers.register_http_action(&method, http_action)
+}
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use hyper::Method;
+use my_http_server::{
+    HttpContext, HttpFailResult, HttpOkResult, HttpOutput, HttpServerMiddleware,
+    HttpServerRequestFlow, WebContentType,
+};
+
+use crate::controllers::{
+    actions::{GetShouldBeAuthorized, HttpActions},
+    documentation::ShouldBeAuthorized,
+    AuthorizationFailContext, ControllersMiddleware,
+};
+
+use super::RouteInfoModel;
+
+pub const DEFAULT_ROUTES_PATH: &str = "/_routes";
+
+/// Lists all registered actions with their effective authorization as JSON.
+/// Access to the endpoint is checked the same way as for any action. The list exposes the whole API,
+/// so authorization is given explicitly: `ShouldBeAuthorized::No` only if the endpoint is not reachable from outside.
+pub struct RoutesIntrospectionMiddleware {
+    controllers: Arc<ControllersMiddleware>,
+    path: String,
+    should_be_authorized: ShouldBeAuthorized,
+}
+
+impl RoutesIntrospectionMiddleware {
+    pub fn new(
+        controllers: Arc<ControllersMiddleware>,
+        should_be_authorized: ShouldBeAuthorized,
+    ) -> Self {
+        Self {
+            controllers,
+            path: DEFAULT_ROUTES_PATH.to_string(),
+            should_be_authorized,
+        }
+    }
+
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = format!("/{}", path.trim_matches('/'));
+        self
+    }
+
+    pub fn get_routes(&self) -> Vec<RouteInfoModel> {
+        let actions = self.controllers.get_actions();
+
+        let mut result = Vec::new();
+        self.populate_routes(&mut result, "GET", &actions.get);
+        self.populate_routes(&mut result, "POST", &actions.post);
+        self.populate_routes(&mut result, "PUT", &actions.put);
+        self.populate_routes(&mut result, "DELETE", &actions.delete);
+        self.populate_routes(&mut result, "PATCH", &actions.patch);
+        self.populate_routes(&mut result, "HEAD", &actions.head);
+        self.populate_routes(&mut result, "OPTIONS", &actions.options);
+        result
+    }
+
+    fn populate_routes(&self, result: &mut Vec<RouteInfoModel>, verb: &str, actions: &HttpActions) {
+        for action in actions.get_actions() {
+            result.push(RouteInfoModel::new(
+                verb,
+                action,
+                &self.controllers.authorization_map,
+            ));
+        }
+    }
+}
+
+impl GetShouldBeAuthorized for RoutesIntrospectionMiddleware {
+    fn get_should_be_authorized(&self) -> &ShouldBeAuthorized {
+        &self.should_be_authorized
+    }
+}
+
+impl RoutesIntrospectionMiddleware {
+    async fn handle_routes_request(
+        &self,
+        ctx: &mut HttpContext,
+    ) -> Option<Result<HttpOkResult, HttpFailResult>> {
+        if ctx.request.get_method() != &Method::GET {
+            return None;
+        }
+
+        if !ctx
+            .request
+            .http_path
+            .as_str()
+            .trim_end_matches('/')
+            .eq_ignore_ascii_case(self.path.as_str())
+        {
+            return None;
+        }
+
+        let fail_context = AuthorizationFailContext {
+            method: &Method::GET,
+            route: self.path.as_str(),
+            action: std::any::type_name::<Self>(),
+        };
+
+        if let Err(err) = self
+            .controllers
+            .authorization_map
+            .authorize(
+                self,
+                &ctx.credentials,
+                ctx.request.get_ip().get_real_ip(),
+                &fail_context,
+                &self.controllers.auth_error_factory,
+            )
+            .await
+        {
+            return Some(Err(err));
+        }
+
+        let output = HttpOutput::Content {
+            headers: None,
+            content_type: Some(WebContentType::Json),
+            content: serde_json::to_vec(&self.get_routes()).unwrap(),
+        };
+
+        Some(output.into_ok_result(false))
+    }
+}
+
+#[async_trait]
+impl HttpServerMiddleware for RoutesIntrospectionMiddleware {
+    async fn handle_request(
+        &self,
+        ctx: &mut HttpContext,
+        get_next: &mut HttpServerRequestFlow,
+    ) -> Result<HttpOkResult, HttpFailResult> {
+        if let Some(result) = self.handle_routes_request(ctx).await {
+            return result;
+        }
+
+        get_next.next(ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controllers::{
+        test_utils::{create_http_context, register_test_action, TestHandler},
+        ControllersAuthorization, OAuth2Settings, RequiredClaims,
+    };
+
+    fn create_controllers() -> Arc<ControllersMiddleware> {
+        let controllers = ControllersMiddleware::new(
+            Some(ControllersAuthorization::OAuth2 {
+                global: true,
+                global_claims: RequiredClaims::parse("admin").unwrap(),
+                settings: OAuth2Settings::client_credentials("https://auth.example.com/token"),
+            }),
+            None,
+        );
+
+        register_test_action(
+            &controllers,
+            Method::GET,
+            "/api/items/{id:int}",
+            Arc::new(TestHandler::new("item")),
+        )
+        .unwrap();
+
+        controllers
+            .create_route_group("/api")
+            .with_should_be_authorized(ShouldBeAuthorized::No)
+            .register_get_action(Arc::new(TestHandler::new("ok").with_route("/status")))
+            .unwrap();
+
+        Arc::new(controllers)
+    }
+
+    fn unwrap_json(result: Option<Result<HttpOkResult, HttpFailResult>>) -> serde_json::Value {
+        match result.unwrap().ok().unwrap().output {
+            HttpOutput::Content { content, .. } => serde_json::from_slice(&content).unwrap(),
+            _ => panic!("Content output is expected"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_are_listed_with_effective_authorization() {
+        let middleware =
+            RoutesIntrospectionMiddleware::new(create_controllers(), ShouldBeAuthorized::No);
+
+        let mut ctx = create_http_context(Method::GET, "/_routes", "127.0.0.1", &[]);
+        let json = unwrap_json(middleware.handle_routes_request(&mut ctx).await);
+
+        assert_eq!(
+            serde_json::json!([
+                {
+                    "verb": "GET",
+                    "route": "/api/items/{id:int}",
+                    "keys": ["id"],
+                    "controller": null,
+                    "action": std::any::type_name::<TestHandler>(),
+                    "authorization": {
+                        "required": true,
+                        "schemes": [{ "scheme": "OAuth2", "claims": "admin" }]
+                    },
+                    "permissions": [],
+                    "enabled": true
+                },
+                {
+                    "verb": "GET",
+                    "route": "/api/status",
+                    "keys": [],
+                    "controller": null,
+                    "action": std::any::type_name::<TestHandler>(),
+                    "authorization": { "required": false, "schemes": [] },
+                    "permissions": [],
+                    "enabled": true
+                }
+            ]),
+            json
+        );
+
+        let mut ctx = create_http_context(Method::GET, "/api/status", "127.0.0.1", &[]);
+        assert!(middleware.handle_routes_request(&mut ctx).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_routes_require_authorization() {
+        let middleware =
+            RoutesIntrospectionMiddleware::new(create_controllers(), ShouldBeAuthorized::Yes)
+                .with_path("/internal/routes/");
+
+        let mut ctx = create_http_context(Method::GET, "/internal/routes", "127.0.0.1", &[]);
+        let result = middleware.handle_routes_request(&mut ctx).await.unwrap();
+
+        assert_eq!(401, result.err().unwrap().status_code);
+    }
+}
@@ -0,0 +1,4 @@
+mod middleware;
+mod models;
+pub use middleware::RoutesIntrospectionMiddleware;
+pub use models::*;
@@ -0,0 +1,85 @@
+use serde::Serialize;
+
+use crate::controllers::{actions::HttpAction, AuthorizationMap, HttpRouteSegment};
+
+#[derive(Serialize, Debug)]
+pub struct RouteInfoModel {
+    pub verb: String,
+    pub route: String,
+    pub keys: Vec<String>,
+    pub controller: Option<String>,
+    pub action: String,
+    pub authorization: RouteAuthorizationModel,
+    pub permissions: Vec<String>,
+    pub enabled: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RouteAuthorizationModel {
+    pub required: bool,
+    pub schemes: Vec<RouteSecuritySchemeModel>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RouteSecuritySchemeModel {
+    pub scheme: String,
+    pub claims: Option<String>,
+}
+
+impl RouteInfoModel {
+    pub fn new(verb: &str, action: &HttpAction, authorization_map: &AuthorizationMap) -> Self {
+        let keys = action
+            .http_route
+            .get_segments()
+            .iter()
+            .filter_map(|segment| match segment {
+                HttpRouteSegment::Key(key) => Some(key.name.to_string()),
+                HttpRouteSegment::Segment(_) => None,
+            })
+            .collect();
+
+        let controller = match &action.swagger_tag {
+            Some(swagger_tag) => Some(swagger_tag.to_string()),
+            None => action
+                .description
+                .get_description()
+                .map(|description| description.controller_name.to_string()),
+        };
+
+        Self {
+            verb: verb.to_string(),
+            route: action.http_route.route.to_string(),
+            keys,
+            controller,
+            action: action.action_type.to_string(),
+            authorization: RouteAuthorizationModel::new(action, authorization_map),
+            permissions: action.required_permissions.clone(),
+            enabled: action.enabled,
+        }
+    }
+}
+
+impl RouteAuthorizationModel {
+    pub fn new(action: &HttpAction, authorization_map: &AuthorizationMap) -> Self {
+        match authorization_map.get_security_requirements(action) {
+            Some(requirements) => Self {
+                required: true,
+                schemes: requirements
+                    .into_iter()
+                    .map(|requirement| RouteSecuritySchemeModel {
+                        scheme: requirement.scheme.as_openid_str().to_string(),
+                        claims: if requirement.claims.has_claims() {
+                            Some(requirement.claims.get_expression().to_string())
+                        } else {
+                            None
+                        },
+                    })
+                    .collect(),
+            },
+            None => Self {
+                required: false,
+                schemes: vec![],
+            },
+        }
+    }
+}
@@ -1,2 +1,3 @@
 pub mod controllers;
+pub mod introspection;
 pub mod swagger;
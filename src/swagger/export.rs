@@ -0,0 +1,119 @@
+use std::path::Path;
+
+use crate::controllers::ControllersMiddleware;
+
+use super::open_api::OpenApiDocument;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpenApiFormat {
+    Yaml,
+    Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenApiExportOptions {
+    pub title: String,
+    pub version: String,
+    /// Server urls in the form of `scheme://host[:port][/base_path]`
+    pub servers: Vec<String>,
+    pub format: OpenApiFormat,
+}
+
+impl OpenApiExportOptions {
+    pub fn new(title: &str, version: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            version: version.to_string(),
+            servers: Vec::new(),
+            format: OpenApiFormat::Yaml,
+        }
+    }
+
+    pub fn with_server(mut self, url: &str) -> Self {
+        self.servers.push(url.to_string());
+        self
+    }
+
+    pub fn with_format(mut self, format: OpenApiFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+pub fn export_openapi_document(
+    controllers: &ControllersMiddleware,
+    options: &OpenApiExportOptions,
+) -> Result<OpenApiDocument, String> {
+    super::open_api_builder::builder::build(
+        controllers,
+        options.title.as_str(),
+        options.version.as_str(),
+        &options.servers,
+    )
+}
+
+/// Builds the same document SwaggerMiddleware serves, without running the server
+pub fn export_openapi(
+    controllers: &ControllersMiddleware,
+    options: &OpenApiExportOptions,
+) -> Result<String, String> {
+    let document = export_openapi_document(controllers, options)?;
+
+    let content = match options.format {
+        OpenApiFormat::Yaml => document.to_yaml(),
+        OpenApiFormat::Json => document.to_json(),
+    };
+
+    Ok(String::from_utf8(content).unwrap())
+}
+
+/// Writes exported document to the file. Missing directories are created.
+pub fn export_openapi_to_file(
+    controllers: &ControllersMiddleware,
+    options: &OpenApiExportOptions,
+    path: impl AsRef<Path>,
+) -> std::io::Result<()> {
+    let path = path.as_ref();
+
+    if let Some(parent) = path.parent() {
+        if parent.as_os_str().len() > 0 {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    let content = export_openapi(controllers, options)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+
+    std::fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_with_servers() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        let options = OpenApiExportOptions::new("Test api", "1.0.0")
+            .with_server("https://api.example.com")
+            .with_server("http://localhost:8080/v2")
+            .with_format(OpenApiFormat::Json);
+
+        let json: serde_json::Value =
+            serde_json::from_str(export_openapi(&controllers, &options).unwrap().as_str()).unwrap();
+
+        assert_eq!(json["openapi"], "3.0.0");
+        assert_eq!(json["servers"][0]["url"], "https://api.example.com");
+        assert_eq!(json["servers"][1]["url"], "http://localhost:8080/v2");
+
+        let yaml: serde_json::Value = serde_yaml::from_str(
+            export_openapi(&controllers, &options.with_format(OpenApiFormat::Yaml))
+                .unwrap()
+                .as_str(),
+        )
+        .unwrap();
+
+        assert_eq!(json, yaml);
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
 use my_http_server::{
@@ -6,12 +6,17 @@ use my_http_server::{
     HttpServerRequestFlow, WebContentType,
 };
 
-use super::super::controllers::ControllersMiddleware;
+use super::{
+    super::controllers::ControllersMiddleware, open_api::OpenApiDocument, OpenApiExportOptions,
+};
+
+const YAML_CONTENT_TYPE: &str = "application/yaml";
 
 pub struct SwaggerMiddleware {
     controllers: Arc<ControllersMiddleware>,
     title: String,
     version: String,
+    oauth_client_id: Option<String>,
 }
 
 impl SwaggerMiddleware {
@@ -20,8 +25,42 @@ impl SwaggerMiddleware {
             controllers,
             title,
             version,
+            oauth_client_id: None,
         }
     }
+
+    /// Client swagger UI uses to run OAuth2 flows. Authorization code flow is executed with PKCE.
+    pub fn with_oauth_client_id(mut self, client_id: &str) -> Self {
+        self.oauth_client_id = Some(client_id.to_string());
+        self
+    }
+
+    fn build_document(&self, ctx: &HttpContext) -> Result<OpenApiDocument, HttpFailResult> {
+        let scheme = ctx.request.get_scheme();
+        let host = ctx.request.get_host();
+
+        let options = OpenApiExportOptions::new(self.title.as_str(), self.version.as_str())
+            .with_server(format!("{}://{}", scheme, host).as_str());
+
+        super::export_openapi_document(self.controllers.as_ref(), &options).map_err(|err| {
+            HttpFailResult::new(WebContentType::Text, 500, err.into_bytes(), false, true)
+        })
+    }
+
+    fn compile_index_page(&self) -> Vec<u8> {
+        let Some(client_id) = &self.oauth_client_id else {
+            return super::resources::INDEX_PAGE.to_vec();
+        };
+
+        let oauth_config = format!(
+            "ui.initOAuth({{ clientId: {}, scopeSeparator: \" \", usePkceWithAuthorizationCodeGrant: true }});",
+            serde_json::to_string(client_id).unwrap()
+        );
+
+        String::from_utf8_lossy(super::resources::INDEX_PAGE)
+            .replace("/*OAUTH_CONFIG*/", oauth_config.as_str())
+            .into_bytes()
+    }
 }
 
 #[async_trait]
@@ -62,7 +101,20 @@ impl HttpServerMiddleware for SwaggerMiddleware {
             let output = HttpOutput::Content {
                 headers: None,
                 content_type: Some(WebContentType::Html),
-                content: super::resources::INDEX_PAGE.to_vec(),
+                content: self.compile_index_page(),
+            };
+            return output.into_ok_result(false);
+        }
+
+        if ctx
+            .request
+            .http_path
+            .has_value_at_index_case_insensitive(1, "oauth2-redirect.html")
+        {
+            let output = HttpOutput::Content {
+                headers: None,
+                content_type: Some(WebContentType::Html),
+                content: super::resources::OAUTH2_REDIRECT_PAGE.to_vec(),
             };
             return output.into_ok_result(false);
         }
@@ -137,26 +189,27 @@ impl HttpServerMiddleware for SwaggerMiddleware {
             .http_path
             .has_values_at_index_case_insensitive(1, &["v1", "swagger.yaml"])
         {
-            let scheme = ctx.request.get_scheme();
-            let host = ctx.request.get_host();
+            let mut headers = HashMap::new();
+            headers.insert("Content-Type".to_string(), YAML_CONTENT_TYPE.to_string());
 
-            let global_fail_resulsts = if let Some(factory) = &self.controllers.auth_error_factory {
-                factory.get_global_http_fail_result_types()
-            } else {
-                None
+            let output = HttpOutput::Content {
+                headers: Some(headers),
+                content_type: None,
+                content: self.build_document(ctx)?.to_yaml(),
             };
 
+            return output.into_ok_result(false);
+        }
+
+        if ctx
+            .request
+            .http_path
+            .has_values_at_index_case_insensitive(1, &["v1", "swagger.json"])
+        {
             let output = HttpOutput::Content {
                 headers: None,
                 content_type: Some(WebContentType::Json),
-                content: super::swagger_yaml::builder::build(
-                    self.controllers.as_ref(),
-                    self.title.as_ref(),
-                    self.version.as_ref(),
-                    host,
-                    scheme.as_ref(),
-                    global_fail_resulsts,
-                ),
+                content: self.build_document(ctx)?.to_json(),
             };
 
             return output.into_ok_result(false);
@@ -1,4 +1,9 @@
+mod export;
 mod middleware;
 mod resources;
+pub use export::*;
 pub use middleware::SwaggerMiddleware;
-mod swagger_yaml;
+pub mod open_api;
+mod open_api_builder;
+mod open_api_diff;
+pub use open_api_diff::*;
@@ -0,0 +1,11 @@
+mod open_api_document;
+mod open_api_map;
+mod open_api_operation;
+mod open_api_schema;
+mod open_api_security_scheme;
+mod yaml_writer;
+pub use open_api_document::*;
+pub use open_api_map::*;
+pub use open_api_operation::*;
+pub use open_api_schema::*;
+pub use open_api_security_scheme::*;
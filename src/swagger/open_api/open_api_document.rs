@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+use super::{OpenApiMap, OpenApiPathItem, OpenApiSchema, OpenApiSecurityScheme};
+
+pub const OPEN_API_VERSION: &str = "3.0.0";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenApiDocument {
+    pub openapi: String,
+    pub info: OpenApiInfo,
+    #[serde(default)]
+    pub servers: Vec<OpenApiServer>,
+    pub components: OpenApiComponents,
+    /// Path in OpenApi format with the operations by verb
+    pub paths: OpenApiMap<OpenApiPathItem>,
+}
+
+impl OpenApiDocument {
+    pub fn new(title: &str, version: &str) -> Self {
+        Self {
+            openapi: OPEN_API_VERSION.to_string(),
+            info: OpenApiInfo {
+                title: title.to_string(),
+                version: version.to_string(),
+            },
+            servers: Vec::new(),
+            components: OpenApiComponents {
+                schemas: OpenApiMap::new(),
+                security_schemes: OpenApiMap::new(),
+            },
+            paths: OpenApiMap::new(),
+        }
+    }
+
+    pub fn from_json(src: &[u8]) -> Result<Self, String> {
+        serde_json::from_slice(src)
+            .map_err(|err| format!("Can not parse OpenApi document: {}", err))
+    }
+
+    pub fn to_json(&self) -> Vec<u8> {
+        serde_json::to_vec_pretty(self).unwrap()
+    }
+
+    pub fn to_yaml(&self) -> Vec<u8> {
+        let value = serde_json::to_value(self).unwrap();
+        super::yaml_writer::YamlWriter::new().write_document(&value)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenApiInfo {
+    pub title: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenApiServer {
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenApiComponents {
+    #[serde(default)]
+    pub schemas: OpenApiMap<OpenApiSchema>,
+    #[serde(default, skip_serializing_if = "OpenApiMap::is_empty")]
+    pub security_schemes: OpenApiMap<OpenApiSecurityScheme>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swagger::open_api::{OpenApiOperation, OpenApiResponse};
+
+    #[test]
+    fn test_yaml_and_json_are_the_same_document() {
+        let mut document = OpenApiDocument::new("Test: api", "1.0");
+
+        document.components.schemas.insert(
+            "Model",
+            OpenApiSchema {
+                description: Some("* 0 [First: case]\n* 1 [#Second]".to_string()),
+                ..OpenApiSchema::of_type("integer")
+            },
+        );
+
+        let mut responses = OpenApiMap::new();
+        responses.insert(
+            "200",
+            OpenApiResponse {
+                description: "Ok".to_string(),
+                content: OpenApiMap::new(),
+            },
+        );
+
+        let mut path_item = OpenApiPathItem::new();
+        path_item.insert(
+            "get",
+            OpenApiOperation {
+                security: None,
+                required_permissions: vec![],
+                tags: vec!["- Tag".to_string()],
+                summary: "Returns 'item': by id".to_string(),
+                description: "Multi\nline \"description\"\n".to_string(),
+                parameters: vec![],
+                request_body: None,
+                responses,
+            },
+        );
+
+        document.paths.insert("/api/{id}", path_item);
+
+        let from_json: serde_json::Value = serde_json::from_slice(&document.to_json()).unwrap();
+        let from_yaml: serde_json::Value = serde_yaml::from_slice(&document.to_yaml()).unwrap();
+
+        assert_eq!(from_json, from_yaml);
+    }
+}
@@ -0,0 +1,120 @@
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Map which keeps insertion order. Swagger UI shows fields, responses and schemas in the order they are declared.
+#[derive(Debug, Clone)]
+pub struct OpenApiMap<T> {
+    items: Vec<(String, T)>,
+}
+
+impl<T> OpenApiMap<T> {
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Replaces the value if key is already in the map. Position of the key is kept.
+    pub fn insert(&mut self, key: impl Into<String>, value: T) {
+        let key = key.into();
+
+        match self.get_mut(key.as_str()) {
+            Some(existing) => *existing = value,
+            None => self.items.push((key, value)),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&T> {
+        self.items
+            .iter()
+            .find(|(itm_key, _)| itm_key == key)
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut T> {
+        self.items
+            .iter_mut()
+            .find(|(itm_key, _)| itm_key == key)
+            .map(|(_, value)| value)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.items.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> Default for OpenApiMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Serialize> Serialize for OpenApiMap<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.items.len()))?;
+
+        for (key, value) in &self.items {
+            map.serialize_entry(key, value)?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for OpenApiMap<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(OpenApiMapVisitor(PhantomData))
+    }
+}
+
+struct OpenApiMapVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for OpenApiMapVisitor<T> {
+    type Value = OpenApiMap<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut result = OpenApiMap::new();
+
+        while let Some((key, value)) = access.next_entry::<String, T>()? {
+            result.insert(key, value);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_insertion_order() {
+        let mut map = OpenApiMap::new();
+        map.insert("b", 1);
+        map.insert("a", 2);
+        map.insert("b", 3);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("b"), Some(&3));
+        assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"b":3,"a":2}"#);
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+use super::{OpenApiMap, OpenApiSchema};
+
+/// Scheme name with the list of scopes. Operation is allowed if any of requirements is satisfied.
+pub type OpenApiSecurityRequirement = OpenApiMap<Vec<String>>;
+
+/// Operations of the path by verb
+pub type OpenApiPathItem = OpenApiMap<OpenApiOperation>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenApiOperation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<OpenApiSecurityRequirement>>,
+    #[serde(
+        default,
+        rename = "x-required-permissions",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub required_permissions: Vec<String>,
+    pub tags: Vec<String>,
+    pub summary: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<OpenApiParameter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<OpenApiRequestBody>,
+    pub responses: OpenApiMap<OpenApiResponse>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenApiParameter {
+    #[serde(rename = "in")]
+    pub location: String,
+    pub name: String,
+    pub description: String,
+    pub schema: OpenApiSchema,
+    pub required: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenApiRequestBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    pub content: OpenApiMap<OpenApiMediaType>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenApiMediaType {
+    pub schema: OpenApiSchema,
+    #[serde(default, skip_serializing_if = "OpenApiMap::is_empty")]
+    pub encoding: OpenApiMap<OpenApiEncoding>,
+}
+
+impl OpenApiMediaType {
+    pub fn new(schema: OpenApiSchema) -> Self {
+        Self {
+            schema,
+            encoding: OpenApiMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenApiEncoding {
+    pub content_type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenApiResponse {
+    pub description: String,
+    #[serde(default, skip_serializing_if = "OpenApiMap::is_empty")]
+    pub content: OpenApiMap<OpenApiMediaType>,
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+use super::OpenApiMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OpenApiSchema {
+    #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<OpenApiMap<OpenApiSchema>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<Box<OpenApiSchema>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<OpenApiSchema>>,
+    #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    pub enum_values: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl OpenApiSchema {
+    pub fn of_type(schema_type: &str) -> Self {
+        Self {
+            schema_type: Some(schema_type.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_format(schema_type: &str, format: &str) -> Self {
+        Self {
+            schema_type: Some(schema_type.to_string()),
+            format: Some(format.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Reference to the schema declared in `components/schemas`
+    pub fn reference(struct_id: &str) -> Self {
+        Self {
+            reference: Some(format!("#/components/schemas/{}", struct_id)),
+            ..Default::default()
+        }
+    }
+
+    pub fn array_of(items: OpenApiSchema) -> Self {
+        Self {
+            schema_type: Some("array".to_string()),
+            items: Some(Box::new(items)),
+            ..Default::default()
+        }
+    }
+
+    pub fn dictionary_of(additional_properties: OpenApiSchema) -> Self {
+        Self {
+            schema_type: Some("object".to_string()),
+            additional_properties: Some(Box::new(additional_properties)),
+            ..Default::default()
+        }
+    }
+
+    pub fn object(properties: OpenApiMap<OpenApiSchema>) -> Self {
+        Self {
+            schema_type: Some("object".to_string()),
+            properties: Some(properties),
+            ..Default::default()
+        }
+    }
+}
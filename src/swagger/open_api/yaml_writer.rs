@@ -0,0 +1,406 @@
+use serde_json::{Map, Value};
+
+const INDENT: usize = 2;
+
+/// Renders json value tree as a block style yaml document
+pub struct YamlWriter {
+    content: Vec<u8>,
+    level: usize,
+}
+
+impl YamlWriter {
+    pub fn new() -> Self {
+        Self {
+            content: Vec::new(),
+            level: 0,
+        }
+    }
+
+    pub fn write_document(mut self, value: &Value) -> Vec<u8> {
+        match value {
+            Value::Object(map) if map.len() > 0 => self.write_map(map, false),
+            Value::Array(items) if items.len() > 0 => self.write_array(items),
+            _ => {
+                self.write_scalar(value);
+                self.new_line();
+            }
+        }
+
+        self.content
+    }
+
+    fn fill_spaces(&mut self) {
+        for _ in 0..self.level * INDENT {
+            self.content.push(b' ');
+        }
+    }
+
+    fn new_line(&mut self) {
+        self.content.push(b'\n');
+    }
+
+    /// First entry of the map, which is an array item, goes right after the `- `
+    fn write_map(&mut self, map: &Map<String, Value>, first_is_inline: bool) {
+        for (index, (key, value)) in map.iter().enumerate() {
+            if index > 0 || !first_is_inline {
+                self.fill_spaces();
+            }
+
+            self.write_string(key, false);
+            self.content.push(b':');
+            self.write_node(value);
+        }
+    }
+
+    fn write_array(&mut self, items: &[Value]) {
+        for item in items {
+            self.fill_spaces();
+            self.content.push(b'-');
+
+            match item {
+                Value::Object(map) if map.len() > 0 => {
+                    self.content.push(b' ');
+                    self.level += 1;
+                    self.write_map(map, true);
+                    self.level -= 1;
+                }
+                _ => self.write_node(item),
+            }
+        }
+    }
+
+    /// Writes value which follows `key:` or `-`
+    fn write_node(&mut self, value: &Value) {
+        match value {
+            Value::Object(map) if map.len() > 0 => {
+                self.new_line();
+                self.level += 1;
+                self.write_map(map, false);
+                self.level -= 1;
+            }
+            Value::Array(items) if items.len() > 0 => {
+                self.new_line();
+                self.level += 1;
+                self.write_array(items);
+                self.level -= 1;
+            }
+            _ => {
+                self.content.push(b' ');
+                self.write_scalar(value);
+                self.new_line();
+            }
+        }
+    }
+
+    fn write_scalar(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.content.extend_from_slice("null".as_bytes()),
+            Value::Bool(value) => self.content.extend_from_slice(value.to_string().as_bytes()),
+            Value::Number(value) => self.content.extend_from_slice(value.to_string().as_bytes()),
+            Value::String(value) => self.write_string(value, true),
+            Value::Array(_) => self.content.extend_from_slice("[]".as_bytes()),
+            Value::Object(_) => self.content.extend_from_slice("{}".as_bytes()),
+        }
+    }
+
+    /// Multi-line values are written as literal block scalars if they can be represented as a block.
+    /// Keys can not be block scalars.
+    fn write_string(&mut self, value: &str, block_allowed: bool) {
+        if block_allowed && value.contains('\n') && can_be_literal_block(value) {
+            self.write_literal_block(value);
+            return;
+        }
+
+        if needs_double_quotes(value) {
+            self.write_double_quoted(value);
+            return;
+        }
+
+        if needs_quotes(value) {
+            self.content.push(b'\'');
+            self.content
+                .extend_from_slice(value.replace('\'', "''").as_bytes());
+            self.content.push(b'\'');
+            return;
+        }
+
+        self.content.extend_from_slice(value.as_bytes());
+    }
+
+    fn write_double_quoted(&mut self, value: &str) {
+        self.content.push(b'"');
+
+        for c in value.chars() {
+            match c {
+                '\\' => self.content.extend_from_slice("\\\\".as_bytes()),
+                '"' => self.content.extend_from_slice("\\\"".as_bytes()),
+                '\n' => self.content.extend_from_slice("\\n".as_bytes()),
+                '\r' => self.content.extend_from_slice("\\r".as_bytes()),
+                '\t' => self.content.extend_from_slice("\\t".as_bytes()),
+                '\0' => self.content.extend_from_slice("\\0".as_bytes()),
+                _ if is_non_printable(c) => self
+                    .content
+                    .extend_from_slice(format!("\\u{:04X}", c as u32).as_bytes()),
+                _ => {
+                    let mut buffer = [0u8; 4];
+                    self.content
+                        .extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                }
+            }
+        }
+
+        self.content.push(b'"');
+    }
+
+    /// Chomping indicator keeps trailing line breaks of the value as they are
+    fn write_literal_block(&mut self, value: &str) {
+        let content = value.trim_end_matches('\n');
+        let trailing_line_breaks = value.len() - content.len();
+
+        self.content.push(b'|');
+
+        match trailing_line_breaks {
+            0 => self.content.push(b'-'),
+            1 => {}
+            _ => self.content.push(b'+'),
+        }
+
+        self.level += 1;
+
+        for line in content.split('\n') {
+            self.new_line();
+
+            if line.len() > 0 {
+                self.fill_spaces();
+                self.content.extend_from_slice(line.as_bytes());
+            }
+        }
+
+        for _ in 1..trailing_line_breaks {
+            self.new_line();
+        }
+
+        self.level -= 1;
+    }
+}
+
+fn is_non_printable(c: char) -> bool {
+    (c.is_control() && c != '\n' && c != '\t')
+        || c == '\u{2028}'
+        || c == '\u{2029}'
+        || c == '\u{feff}'
+}
+
+fn needs_double_quotes(value: &str) -> bool {
+    value
+        .chars()
+        .any(|c| c == '\n' || c == '\t' || is_non_printable(c))
+}
+
+/// Indentation of the block is detected by the first non empty line,
+/// so it can not start with a space. Lines with spaces only would lose them.
+fn can_be_literal_block(value: &str) -> bool {
+    if value.chars().any(is_non_printable) {
+        return false;
+    }
+
+    let content = value.trim_end_matches('\n');
+
+    if content.len() == 0 {
+        return false;
+    }
+
+    let mut first_non_empty = true;
+
+    for line in content.split('\n') {
+        if line.len() == 0 {
+            continue;
+        }
+
+        if line.trim_start_matches(' ').len() == 0 {
+            return false;
+        }
+
+        if first_non_empty && (line.starts_with(' ') || line.starts_with('\t')) {
+            return false;
+        }
+
+        first_non_empty = false;
+    }
+
+    true
+}
+
+/// Plain scalar has to be read back as the same string: no indicators, comments, line breaks
+/// and no values which are resolved as booleans, nulls or numbers.
+fn needs_quotes(value: &str) -> bool {
+    if value.len() == 0 {
+        return true;
+    }
+
+    if value.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`".contains(c)) {
+        return true;
+    }
+
+    if value.starts_with(char::is_whitespace) || value.ends_with(char::is_whitespace) {
+        return true;
+    }
+
+    if value.contains(": ") || value.contains(" #") || value.ends_with(':') {
+        return true;
+    }
+
+    if value.starts_with("...") || value == "<<" {
+        return true;
+    }
+
+    is_resolved_as_other_type(value)
+}
+
+fn is_resolved_as_other_type(value: &str) -> bool {
+    let lower_case = value.to_lowercase();
+
+    if [
+        "true", "false", "yes", "no", "on", "off", "y", "n", "null", "~", ".inf", "+.inf", "-.inf",
+        ".nan",
+    ]
+    .contains(&lower_case.as_str())
+    {
+        return true;
+    }
+
+    let mut chars = value.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_digit() => true,
+        Some('+') | Some('.') => matches!(chars.next(), Some(c) if c.is_ascii_digit()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn assert_round_trip(value: Value) {
+        let yaml = YamlWriter::new().write_document(&value);
+        let yaml = String::from_utf8(yaml).unwrap();
+
+        let parsed: Value =
+            serde_yaml::from_str(yaml.as_str()).unwrap_or_else(|err| panic!("{}\n{}", err, yaml));
+
+        assert_eq!(parsed, value, "\n{}", yaml);
+    }
+
+    #[test]
+    fn test_scalars_round_trip() {
+        let values = [
+            "plain text",
+            "key: value",
+            "# not a comment",
+            "text #with hash",
+            "- not an item",
+            "-1",
+            "ends with:",
+            "'single'",
+            "\"double\"",
+            "it's",
+            "back\\slash",
+            "{braces}",
+            "[brackets]",
+            "&anchor",
+            "*alias",
+            "!tag",
+            "|pipe",
+            ">folded",
+            "%percent",
+            "@at",
+            "`tick",
+            "?question",
+            "",
+            " leading space",
+            "trailing space ",
+            "true",
+            "No",
+            "null",
+            "~",
+            "123",
+            "1.5",
+            ".5",
+            "0x1F",
+            "1.0.0",
+            "2023-01-01",
+            "...",
+            "---",
+            "<<",
+            "tab\tinside",
+            "bell\u{7}",
+            "unicode ✓ текст",
+            "line\u{2028}separator",
+            "#/components/schemas/Model",
+            "^[a-zA-Z]+$",
+        ];
+
+        for value in values {
+            assert_round_trip(json!({ "field": value }));
+            assert_round_trip(json!({ value: "key" }));
+            assert_round_trip(json!([value]));
+        }
+    }
+
+    #[test]
+    fn test_multi_line_round_trip() {
+        let values = [
+            "first\nsecond",
+            "ends with line break\n",
+            "two line breaks\n\n",
+            "\nstarts with line break",
+            "empty\n\nline inside",
+            " indented\nfirst line",
+            "second\n  indented",
+            "spaces only\n   \nline",
+            "windows\r\nline breaks",
+            "* 0 [Case]\n* 1 [Other: case]",
+            "\n",
+        ];
+
+        for value in values {
+            assert_round_trip(json!({ "description": value }));
+            assert_round_trip(json!({ "nested": { "description": value } }));
+            assert_round_trip(json!({ "items": [value, { "description": value }] }));
+            assert_round_trip(json!({ value: "key" }));
+        }
+    }
+
+    #[test]
+    fn test_structures_round_trip() {
+        assert_round_trip(json!({
+            "openapi": "3.0.0",
+            "empty_map": {},
+            "empty_array": [],
+            "numbers": [1, -2, 3.5],
+            "flags": [true, false, null],
+            "200": { "description": "Ok" },
+            "objects": [
+                { "name": "first", "schema": { "type": "string" }, "tags": ["a", "b"] },
+                { "nested": [[1, 2], [{ "a": 1 }]] }
+            ]
+        }));
+    }
+
+    #[test]
+    fn test_uses_plain_scalars_and_lf() {
+        let yaml = YamlWriter::new().write_document(&json!({
+            "summary": "Get item",
+            "$ref": "#/components/schemas/Model",
+            "description": "first\nsecond"
+        }));
+
+        assert_eq!(
+            String::from_utf8(yaml).unwrap(),
+            "summary: Get item\n$ref: '#/components/schemas/Model'\ndescription: |-\n  first\n  second\n"
+        );
+    }
+}
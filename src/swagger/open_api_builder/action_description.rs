@@ -0,0 +1,23 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::controllers::{actions::HttpAction, documentation::HttpActionDescription};
+
+/// Description of the action together with the action it was taken from
+pub struct SwaggerActionDescription<'s> {
+    pub action: &'s HttpAction,
+    pub inner: HttpActionDescription<'s>,
+}
+
+impl<'s> Deref for SwaggerActionDescription<'s> {
+    type Target = HttpActionDescription<'s>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<'s> DerefMut for SwaggerActionDescription<'s> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
@@ -0,0 +1,458 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{
+    controllers::{
+        actions::HttpAction, documentation::out_results::HttpResult, ControllersActions,
+        ControllersMiddleware,
+    },
+    swagger::open_api::{OpenApiDocument, OpenApiServer},
+};
+
+use super::action_description::SwaggerActionDescription;
+
+pub fn build(
+    controllers: &ControllersMiddleware,
+    title: &str,
+    version: &str,
+    servers: &[String],
+) -> Result<OpenApiDocument, String> {
+    let mut document = OpenApiDocument::new(title, version);
+
+    for url in servers {
+        document.servers.push(OpenApiServer {
+            url: url.to_string(),
+        });
+    }
+
+    let global_fail_results = if let Some(factory) = &controllers.auth_error_factory {
+        factory.get_global_http_fail_result_types()
+    } else {
+        None
+    };
+
+    let actions = controllers.get_actions();
+    let path_descriptions = build_paths_descriptions(&actions, global_fail_results);
+
+    document.components.schemas = super::definitions::build(controllers, &path_descriptions);
+    document.components.security_schemes =
+        super::security_definitions::build(&controllers.authorization_map.schemes);
+
+    document.paths = super::paths::build(&path_descriptions, controllers);
+
+    Ok(document)
+}
+
+fn build_paths_descriptions<'s>(
+    actions: &'s ControllersActions,
+    global_fail_results: Option<Vec<HttpResult>>,
+) -> BTreeMap<String, BTreeMap<String, SwaggerActionDescription<'s>>> {
+    let mut result = BTreeMap::new();
+
+    populate_paths_descriptions(&mut result, "get", actions.get.get_actions());
+    populate_paths_descriptions(&mut result, "post", actions.post.get_actions());
+    populate_paths_descriptions(&mut result, "put", actions.put.get_actions());
+    populate_paths_descriptions(&mut result, "delete", actions.delete.get_actions());
+    populate_paths_descriptions(&mut result, "patch", actions.patch.get_actions());
+    populate_paths_descriptions(&mut result, "head", actions.head.get_actions());
+    populate_paths_descriptions(&mut result, "options", actions.options.get_actions());
+
+    if let Some(global_path_description) = global_fail_results {
+        for verbs in result.values_mut() {
+            for action in verbs.values_mut() {
+                for global_fail_result in &global_path_description {
+                    action.results.push(global_fail_result.clone());
+                }
+            }
+        }
+    }
+
+    result
+}
+
+fn populate_paths_descriptions<'s>(
+    result: &mut BTreeMap<String, BTreeMap<String, SwaggerActionDescription<'s>>>,
+    verb: &str,
+    route_actions: &'s [Arc<HttpAction>],
+) {
+    for route_action in route_actions {
+        if !route_action.enabled {
+            continue;
+        }
+
+        if let Some(mut description) = route_action.description.get_description() {
+            description.should_be_authorized = route_action.should_be_authorized.clone();
+
+            if let Some(swagger_tag) = &route_action.swagger_tag {
+                description.controller_name = swagger_tag.as_str();
+            }
+
+            if !result.contains_key(route_action.http_route.route.as_str()) {
+                result.insert(route_action.http_route.route.to_string(), BTreeMap::new());
+            }
+
+            result
+                .get_mut(route_action.http_route.route.as_str())
+                .unwrap()
+                .insert(
+                    verb.to_string(),
+                    SwaggerActionDescription {
+                        action: route_action,
+                        inner: description,
+                    },
+                );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use hyper::Method;
+
+    use super::*;
+    use crate::controllers::{
+        documentation::{
+            data_types::{HttpDataType, HttpField, HttpSimpleType},
+            in_parameters::{HttpInputParameter, HttpParameterInputSource},
+            HttpActionDescription, ShouldBeAuthorized,
+        },
+        test_utils::{register_test_action, TestHandler},
+        ControllersAuthorization, OAuth2Settings, RequiredClaims,
+    };
+
+    fn create_param(
+        name: &str,
+        simple_type: HttpSimpleType,
+        required: bool,
+        source: HttpParameterInputSource,
+    ) -> HttpInputParameter {
+        HttpInputParameter {
+            field: HttpField::new(name, HttpDataType::SimpleType(simple_type), required),
+            description: format!("{} parameter", name),
+            source,
+        }
+    }
+
+    fn create_result(http_code: u16, description: &str) -> HttpResult {
+        HttpResult {
+            http_code,
+            nullable: false,
+            description: description.to_string(),
+            data_type: HttpDataType::SimpleType(HttpSimpleType::String),
+        }
+    }
+
+    fn get_item_description() -> HttpActionDescription<'static> {
+        HttpActionDescription {
+            controller_name: "Items",
+            summary: "Get item",
+            description: "Returns item by id",
+            input_params: vec![
+                create_param(
+                    "id",
+                    HttpSimpleType::Integer,
+                    true,
+                    HttpParameterInputSource::Path,
+                ),
+                create_param(
+                    "expand",
+                    HttpSimpleType::Boolean,
+                    false,
+                    HttpParameterInputSource::Query,
+                ),
+            ]
+            .into(),
+            results: vec![create_result(200, "Item")],
+            should_be_authorized: ShouldBeAuthorized::No,
+        }
+    }
+
+    fn delete_item_description() -> HttpActionDescription<'static> {
+        HttpActionDescription {
+            controller_name: "Items",
+            summary: "Delete item",
+            description: "Deletes item by id",
+            input_params: vec![create_param(
+                "id",
+                HttpSimpleType::String,
+                true,
+                HttpParameterInputSource::Path,
+            )]
+            .into(),
+            results: vec![create_result(204, "Deleted")],
+            should_be_authorized: ShouldBeAuthorized::No,
+        }
+    }
+
+    fn create_item_description() -> HttpActionDescription<'static> {
+        HttpActionDescription {
+            controller_name: "Items",
+            summary: "Create item",
+            description: "Creates item",
+            input_params: vec![create_param(
+                "name",
+                HttpSimpleType::String,
+                true,
+                HttpParameterInputSource::BodyRaw,
+            )]
+            .into(),
+            results: vec![create_result(201, "Created")],
+            should_be_authorized: ShouldBeAuthorized::No,
+        }
+    }
+
+    fn register(
+        controllers: &ControllersMiddleware,
+        method: Method,
+        route: &str,
+        description: fn() -> HttpActionDescription<'static>,
+    ) {
+        let handler = Arc::new(TestHandler::new("ok").with_description(description));
+        register_test_action(controllers, method, route, handler).unwrap();
+    }
+
+    #[test]
+    fn test_document_of_registered_actions() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        register(
+            &controllers,
+            Method::GET,
+            "/api/items/{id:int}",
+            get_item_description,
+        );
+        register(
+            &controllers,
+            Method::DELETE,
+            "/api/items/{id}",
+            delete_item_description,
+        );
+        register(
+            &controllers,
+            Method::POST,
+            "/api/items",
+            create_item_description,
+        );
+
+        let document = build(&controllers, "Test api", "1.0", &[]).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&document.to_json()).unwrap();
+
+        let paths = json["paths"].as_object().unwrap();
+        assert_eq!(2, paths.len());
+
+        let item_path = &json["paths"]["/api/items/{id}"];
+
+        let get = &item_path["get"];
+        assert_eq!("Get item", get["summary"]);
+        assert_eq!("Items", get["tags"][0]);
+        assert_eq!("path", get["parameters"][0]["in"]);
+        assert_eq!("id", get["parameters"][0]["name"]);
+        assert_eq!(true, get["parameters"][0]["required"]);
+        assert_eq!("integer", get["parameters"][0]["schema"]["type"]);
+        assert_eq!("query", get["parameters"][1]["in"]);
+        assert_eq!("expand", get["parameters"][1]["name"]);
+        assert_eq!(false, get["parameters"][1]["required"]);
+        assert_eq!("Item", get["responses"]["200"]["description"]);
+
+        // Operation of the route without constraint is not lost
+        let delete = &item_path["delete"];
+        assert_eq!("Delete item", delete["summary"]);
+        assert_eq!("string", delete["parameters"][0]["schema"]["type"]);
+        assert_eq!("Deleted", delete["responses"]["204"]["description"]);
+
+        let post = &json["paths"]["/api/items"]["post"];
+        assert_eq!(true, post["requestBody"]["required"]);
+        assert_eq!(
+            "string",
+            post["requestBody"]["content"]["application/json"]["schema"]["type"]
+        );
+        assert_eq!("Created", post["responses"]["201"]["description"]);
+    }
+
+    #[test]
+    fn test_document_reflects_current_actions() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        register(
+            &controllers,
+            Method::GET,
+            "/api/items/{id}",
+            get_item_description,
+        );
+        register(
+            &controllers,
+            Method::POST,
+            "/api/items",
+            create_item_description,
+        );
+
+        let document = build(&controllers, "Test api", "1.0", &[]).unwrap();
+        assert_eq!(2, document.paths.len());
+
+        controllers
+            .unregister_action(&Method::POST, "/api/items")
+            .unwrap();
+
+        let document = build(&controllers, "Test api", "1.0", &[]).unwrap();
+        assert_eq!(1, document.paths.len());
+        assert!(document.paths.get("/api/items/{id}").is_some());
+
+        controllers
+            .set_action_enabled(&Method::GET, "/api/items/{id}", false)
+            .unwrap();
+
+        let document = build(&controllers, "Test api", "1.0", &[]).unwrap();
+        assert_eq!(0, document.paths.len());
+    }
+
+    #[test]
+    fn test_same_verb_on_same_open_api_path_is_noted() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        register(
+            &controllers,
+            Method::GET,
+            "/api/items/{id:int}",
+            get_item_description,
+        );
+        register(
+            &controllers,
+            Method::GET,
+            "/api/items/{id:alpha}",
+            get_item_description,
+        );
+
+        let document = build(&controllers, "Test api", "1.0", &[]).unwrap();
+        assert_eq!(1, document.paths.len());
+
+        let path_item = document.paths.get("/api/items/{id}").unwrap();
+        assert_eq!(1, path_item.len());
+
+        let get = path_item.get("get").unwrap();
+        assert!(get.description.starts_with("Returns item by id"));
+        assert!(get.description.contains("/api/items/{id:int}"));
+    }
+
+    fn get_report_description() -> HttpActionDescription<'static> {
+        HttpActionDescription {
+            controller_name: "Reports",
+            summary: "Get report",
+            description: "Returns report of the year or of the month",
+            input_params: vec![
+                create_param(
+                    "year",
+                    HttpSimpleType::Integer,
+                    true,
+                    HttpParameterInputSource::Path,
+                ),
+                create_param(
+                    "month",
+                    HttpSimpleType::Integer,
+                    false,
+                    HttpParameterInputSource::Path,
+                ),
+            ]
+            .into(),
+            results: vec![create_result(200, "Report")],
+            should_be_authorized: ShouldBeAuthorized::No,
+        }
+    }
+
+    #[test]
+    fn test_optional_key_is_described_per_arity() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        register(
+            &controllers,
+            Method::GET,
+            "/api/reports/{year}/{month:int=1}",
+            get_report_description,
+        );
+
+        let document = build(&controllers, "Test api", "1.0", &[]).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&document.to_json()).unwrap();
+
+        let year_params = json["paths"]["/api/reports/{year}"]["get"]["parameters"]
+            .as_array()
+            .unwrap();
+        assert_eq!(1, year_params.len());
+        assert_eq!("year", year_params[0]["name"]);
+
+        let month_params = json["paths"]["/api/reports/{year}/{month}"]["get"]["parameters"]
+            .as_array()
+            .unwrap();
+        assert_eq!(2, month_params.len());
+        assert_eq!("month", month_params[1]["name"]);
+        assert_eq!(true, month_params[1]["required"]);
+        assert_eq!(serde_json::json!(1), month_params[1]["schema"]["default"]);
+    }
+
+    #[test]
+    fn test_result_content_type_is_set_per_action() {
+        let controllers = ControllersMiddleware::new(None, None);
+
+        register(
+            &controllers,
+            Method::GET,
+            "/api/items/{id}",
+            get_item_description,
+        );
+
+        controllers
+            .set_result_content_type(&Method::GET, "/api/items/{id}", 200, "image/png")
+            .unwrap();
+
+        assert!(controllers
+            .set_result_content_type(&Method::GET, "/api/unknown", 200, "image/png")
+            .is_err());
+
+        let document = build(&controllers, "Test api", "1.0", &[]).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&document.to_json()).unwrap();
+
+        let content = json["paths"]["/api/items/{id}"]["get"]["responses"]["200"]["content"]
+            .as_object()
+            .unwrap();
+
+        assert_eq!(1, content.len());
+        assert_eq!("string", content["image/png"]["schema"]["type"]);
+    }
+
+    fn list_items_description() -> HttpActionDescription<'static> {
+        HttpActionDescription {
+            controller_name: "Items",
+            summary: "List items",
+            description: "Returns all items",
+            input_params: vec![].into(),
+            results: vec![create_result(200, "Items")],
+            should_be_authorized: ShouldBeAuthorized::UseGlobal,
+        }
+    }
+
+    #[test]
+    fn test_any_of_claims_are_separate_security_requirements() {
+        let controllers = ControllersMiddleware::new(
+            Some(ControllersAuthorization::OAuth2 {
+                global: true,
+                global_claims: RequiredClaims::parse("admin | (support & read) & !guest").unwrap(),
+                settings: OAuth2Settings::client_credentials("https://auth.example.com/token"),
+            }),
+            None,
+        );
+
+        register(
+            &controllers,
+            Method::GET,
+            "/api/items",
+            list_items_description,
+        );
+
+        let document = build(&controllers, "Test api", "1.0", &[]).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&document.to_json()).unwrap();
+
+        assert_eq!(
+            serde_json::json!([{ "OAuth2": ["admin"] }, { "OAuth2": ["support", "read"] }]),
+            json["paths"]["/api/items"]["get"]["security"]
+        );
+    }
+}
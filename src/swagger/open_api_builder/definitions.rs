@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    controllers::{
+        documentation::{
+            data_types::{ArrayElement, HttpDataType, HttpEnumStructure, HttpObjectStructure},
+            InputStructure,
+        },
+        ControllersMiddleware,
+    },
+    swagger::open_api::{OpenApiMap, OpenApiSchema},
+};
+
+use super::action_description::SwaggerActionDescription;
+
+pub fn build(
+    controllers: &ControllersMiddleware,
+    path_descriptions: &BTreeMap<String, BTreeMap<String, SwaggerActionDescription>>,
+) -> OpenApiMap<OpenApiSchema> {
+    let mut definitions = OpenApiMap::new();
+
+    for http_object in &controllers.http_objects {
+        write_object_type(&mut definitions, http_object);
+    }
+
+    for (_, action_descriptions) in path_descriptions {
+        for (_, action_description) in action_descriptions {
+            for result in &action_description.results {
+                populate_object_type(&mut definitions, &result.data_type);
+            }
+
+            if let Some(input_parameters) = action_description.input_params.get_body_params() {
+                for in_param in input_parameters {
+                    populate_object_type(&mut definitions, &in_param.field.data_type);
+                }
+            }
+
+            if let Some(input_parameters) = action_description.input_params.get_non_body_params() {
+                for in_param in input_parameters {
+                    populate_object_type(&mut definitions, &in_param.field.data_type);
+                }
+            }
+
+            if let Some(input_parameters) = action_description.input_params.get_form_data_params() {
+                for in_param in input_parameters {
+                    populate_object_type(&mut definitions, &in_param.field.data_type);
+                }
+            }
+        }
+    }
+
+    definitions
+}
+
+fn populate_object_type(definitions: &mut OpenApiMap<OpenApiSchema>, data_type: &HttpDataType) {
+    match data_type {
+        HttpDataType::SimpleType(_) => {}
+        HttpDataType::Object(object_type) => {
+            write_object_type(definitions, object_type);
+        }
+
+        HttpDataType::ArrayOf(array_element) => {
+            populate_array_type(definitions, array_element);
+        }
+        HttpDataType::Enum(enum_structure) => {
+            write_enum_type(definitions, enum_structure);
+        }
+
+        HttpDataType::None => {}
+        HttpDataType::DictionaryOf(array_element) => {
+            populate_array_type(definitions, array_element);
+        }
+        HttpDataType::DictionaryOfArray(array_element) => {
+            populate_array_type(definitions, array_element);
+        }
+    }
+}
+
+fn populate_array_type(definitions: &mut OpenApiMap<OpenApiSchema>, array_element: &ArrayElement) {
+    match array_element {
+        ArrayElement::SimpleType(_) => {}
+        ArrayElement::Object(object_type) => write_object_type(definitions, object_type),
+        ArrayElement::Enum(enum_structure) => {
+            write_enum_type(definitions, enum_structure);
+        }
+    }
+}
+
+fn write_object_type(
+    definitions: &mut OpenApiMap<OpenApiSchema>,
+    object_type: &HttpObjectStructure,
+) {
+    let struct_id = object_type.get_struct_id();
+
+    if !definitions.contains_key(struct_id.as_str()) {
+        definitions.insert(
+            struct_id.as_str(),
+            super::http_object_type::build(&object_type.main),
+        );
+
+        for field in &object_type.main.fields {
+            populate_object_type(definitions, &field.data_type);
+        }
+    }
+
+    if let Some(generic_data) = &object_type.generic {
+        if !definitions.contains_key(generic_data.struct_id) {
+            definitions.insert(
+                generic_data.struct_id,
+                super::http_object_type::build(generic_data),
+            );
+
+            for field in &generic_data.fields {
+                populate_object_type(definitions, &field.data_type);
+            }
+        }
+    }
+}
+
+fn write_enum_type(
+    definitions: &mut OpenApiMap<OpenApiSchema>,
+    enum_structure: &HttpEnumStructure,
+) {
+    if definitions.contains_key(enum_structure.struct_id) {
+        return;
+    };
+
+    definitions.insert(
+        enum_structure.struct_id,
+        super::http_enum_type::build(enum_structure),
+    );
+}
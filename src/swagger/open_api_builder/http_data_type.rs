@@ -0,0 +1,48 @@
+use crate::{
+    controllers::documentation::{
+        data_types::{ArrayElement, EnumType, HttpDataType, HttpSimpleType},
+        InputStructure,
+    },
+    swagger::open_api::OpenApiSchema,
+};
+
+pub fn build(data_type: &HttpDataType) -> Option<OpenApiSchema> {
+    let result = match data_type {
+        HttpDataType::SimpleType(param_type) => build_simple_type(param_type),
+        HttpDataType::Object(object_type) => {
+            OpenApiSchema::reference(object_type.get_struct_id().as_str())
+        }
+        HttpDataType::Enum(enum_type) => match enum_type.enum_type {
+            EnumType::Integer => build_simple_type(&HttpSimpleType::Integer),
+            EnumType::String => build_simple_type(&HttpSimpleType::String),
+        },
+        HttpDataType::None => return None,
+        HttpDataType::ArrayOf(array_element) => {
+            OpenApiSchema::array_of(build_array_element(array_element))
+        }
+        HttpDataType::DictionaryOf(array_element) => {
+            OpenApiSchema::dictionary_of(build_array_element(array_element))
+        }
+        HttpDataType::DictionaryOfArray(array_element) => OpenApiSchema::dictionary_of(
+            OpenApiSchema::array_of(build_array_element(array_element)),
+        ),
+    };
+
+    Some(result)
+}
+
+pub fn build_simple_type(param_type: &HttpSimpleType) -> OpenApiSchema {
+    OpenApiSchema::with_format(param_type.as_swagger_type(), param_type.as_format())
+}
+
+fn build_array_element(array_element: &ArrayElement) -> OpenApiSchema {
+    match array_element {
+        ArrayElement::SimpleType(param_type) => build_simple_type(param_type),
+        ArrayElement::Object(object_type) => {
+            OpenApiSchema::reference(object_type.get_struct_id().as_str())
+        }
+        ArrayElement::Enum(enum_type) => {
+            OpenApiSchema::reference(enum_type.get_struct_id().as_str())
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use serde_json::Value;
+
+use crate::{
+    controllers::documentation::data_types::{EnumType, HttpEnumStructure},
+    swagger::open_api::OpenApiSchema,
+};
+
+pub fn build(enum_structure: &HttpEnumStructure) -> OpenApiSchema {
+    let mut result = match enum_structure.enum_type {
+        EnumType::Integer => OpenApiSchema::of_type("integer"),
+        EnumType::String => OpenApiSchema::of_type("string"),
+    };
+
+    result.enum_values = enum_structure
+        .cases
+        .iter()
+        .map(|case| match enum_structure.enum_type {
+            EnumType::Integer => match case.value.parse::<i64>() {
+                Ok(value) => Value::from(value),
+                Err(_) => Value::from(case.value),
+            },
+            EnumType::String => Value::from(case.value),
+        })
+        .collect();
+
+    let description: Vec<String> = enum_structure
+        .cases
+        .iter()
+        .map(|case| format!("* {} [{}]", case.id, case.description))
+        .collect();
+
+    result.description = Some(description.join("\n"));
+
+    result
+}
@@ -0,0 +1,25 @@
+use crate::{
+    controllers::documentation::HttpObjectFields,
+    swagger::open_api::{OpenApiMap, OpenApiSchema},
+};
+
+pub fn build(data: &HttpObjectFields) -> OpenApiSchema {
+    let mut properties = OpenApiMap::new();
+
+    for field in &data.fields {
+        if let Some(schema) = super::http_data_type::build(&field.data_type) {
+            properties.insert(field.name.as_str(), schema);
+        }
+    }
+
+    let mut result = OpenApiSchema::object(properties);
+
+    result.required = data
+        .fields
+        .iter()
+        .filter(|itm| itm.required)
+        .map(|itm| itm.name.to_string())
+        .collect();
+
+    result
+}
@@ -0,0 +1,64 @@
+use crate::{
+    controllers::documentation::{
+        ArrayElement, HttpDataType, HttpField, HttpObjectStructure, InputStructure,
+    },
+    swagger::open_api::{OpenApiMap, OpenApiSchema},
+};
+
+pub fn build(field: &HttpField) -> Option<OpenApiSchema> {
+    let result = match &field.data_type {
+        HttpDataType::SimpleType(simple_type) => {
+            super::http_data_type::build_simple_type(simple_type)
+        }
+        HttpDataType::Object(object) => build_body_object_type(object),
+        HttpDataType::ArrayOf(array_el) => build_body_array_type(array_el),
+        HttpDataType::DictionaryOf(array_el) => match array_el {
+            ArrayElement::SimpleType(simple_type) => {
+                OpenApiSchema::dictionary_of(super::http_data_type::build_simple_type(simple_type))
+            }
+            ArrayElement::Object(obj) => OpenApiSchema::dictionary_of(build_body_object_type(obj)),
+            ArrayElement::Enum(enum_type) => {
+                panic!(
+                    "Enum in dictionary of enum is not supported: {:?}",
+                    enum_type
+                );
+            }
+        },
+        HttpDataType::DictionaryOfArray(array_el) => {
+            OpenApiSchema::dictionary_of(build_body_array_type(array_el))
+        }
+        HttpDataType::Enum(enum_data) => {
+            OpenApiSchema::reference(enum_data.get_struct_id().as_str())
+        }
+        HttpDataType::None => return None,
+    };
+
+    Some(result)
+}
+
+fn build_body_object_type(object: &HttpObjectStructure) -> OpenApiSchema {
+    let mut properties = OpenApiMap::new();
+
+    for obj_field in &object.main.fields {
+        if let Some(schema) = build(obj_field) {
+            properties.insert(obj_field.name.as_str(), schema);
+        }
+    }
+
+    OpenApiSchema::object(properties)
+}
+
+fn build_body_array_type(array_el: &ArrayElement) -> OpenApiSchema {
+    match array_el {
+        ArrayElement::SimpleType(simple_type) => {
+            OpenApiSchema::array_of(super::http_data_type::build_simple_type(simple_type))
+        }
+        ArrayElement::Object(obj) => OpenApiSchema::array_of(build_body_object_type(obj)),
+        ArrayElement::Enum(enum_data) => {
+            panic!(
+                "Enum in array not supported yet as a from data. {:?}",
+                enum_data
+            );
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    controllers::{documentation::HttpActionDescription, HttpRoute},
+    swagger::open_api::{
+        OpenApiEncoding, OpenApiMap, OpenApiMediaType, OpenApiParameter, OpenApiRequestBody,
+        OpenApiSchema,
+    },
+};
+
+use super::{in_param_as_body, in_param_as_from_data};
+
+/// Path params of omitted keys are not a part of the described path
+pub fn build(
+    http_route: &HttpRoute,
+    omitted_keys: &[&str],
+    action_description: &HttpActionDescription,
+) -> (Vec<OpenApiParameter>, Option<OpenApiRequestBody>) {
+    let mut parameters = Vec::new();
+
+    if let Some(non_body_params) = action_description.input_params.get_non_body_params() {
+        for param in non_body_params {
+            if param.source.is_path() && omitted_keys.contains(&param.field.name.as_str()) {
+                continue;
+            }
+
+            parameters.push(super::query_params::build_query_input_param(
+                http_route, param,
+            ));
+        }
+    }
+
+    (parameters, build_request_body(action_description))
+}
+
+fn build_request_body(action_description: &HttpActionDescription) -> Option<OpenApiRequestBody> {
+    if let Some(body_param) = action_description.input_params.is_single_body_parameter() {
+        let mut content = OpenApiMap::new();
+
+        if let Some(schema) = super::http_data_type::build(&body_param.field.data_type) {
+            content.insert("application/json", OpenApiMediaType::new(schema));
+        }
+
+        return Some(OpenApiRequestBody {
+            description: Some(body_param.description.to_string()),
+            required: Some(true),
+            content,
+        });
+    }
+
+    if let Some(body_params) = action_description.input_params.get_body_params() {
+        let mut properties = OpenApiMap::new();
+
+        for param in body_params {
+            if let Some(schema) = in_param_as_body::build(&param.field) {
+                properties.insert(param.field.name.as_str(), schema);
+            }
+        }
+
+        let mut content = OpenApiMap::new();
+        content.insert(
+            "application/json",
+            OpenApiMediaType::new(OpenApiSchema::object(properties)),
+        );
+
+        return Some(OpenApiRequestBody {
+            description: None,
+            required: Some(true),
+            content,
+        });
+    }
+
+    if let Some(form_data_params) = action_description.input_params.get_form_data_params() {
+        let mut properties = OpenApiMap::new();
+        let mut encoding = OpenApiMap::new();
+
+        for param in form_data_params {
+            if let Some(schema) = in_param_as_from_data::build(&param.field) {
+                properties.insert(param.field.name.as_str(), schema);
+            }
+
+            if param.field.data_type.is_object() {
+                encoding.insert(
+                    param.field.name.as_str(),
+                    OpenApiEncoding {
+                        content_type: "application/json".to_string(),
+                    },
+                );
+            }
+        }
+
+        let mut content = OpenApiMap::new();
+        content.insert(
+            "multipart/form-data",
+            OpenApiMediaType {
+                schema: OpenApiSchema::object(properties),
+                encoding,
+            },
+        );
+
+        return Some(OpenApiRequestBody {
+            description: None,
+            required: None,
+            content,
+        });
+    }
+
+    None
+}
@@ -1,3 +1,4 @@
+mod action_description;
 pub mod builder;
 mod definitions;
 mod http_data_type;
@@ -6,9 +7,7 @@ mod http_object_type;
 mod in_param_as_body;
 mod in_param_as_from_data;
 mod in_parameters;
-pub mod object;
 mod paths;
 mod query_params;
 mod security_definitions;
 mod verb_description;
-mod yaml_writer;
@@ -0,0 +1,61 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{
+    controllers::ControllersMiddleware,
+    swagger::open_api::{OpenApiMap, OpenApiPathItem},
+};
+
+use super::action_description::SwaggerActionDescription;
+
+/// Routes which differ only by constraints, like `/items/{id:int}` and `/items/{id}`, are the same OpenApi path.
+/// Their operations are merged into one path item. The same verb twice on the path can not be described -
+/// the first route is described and the description of its operation mentions the other ones.
+/// Route with optional keys is described as one path per arity.
+pub fn build(
+    actions: &BTreeMap<String, BTreeMap<String, SwaggerActionDescription>>,
+    controllers: &ControllersMiddleware,
+) -> OpenApiMap<OpenApiPathItem> {
+    let mut result: OpenApiMap<OpenApiPathItem> = OpenApiMap::new();
+    let mut described_routes: HashSet<(String, &str)> = HashSet::new();
+
+    for (route, actions) in actions {
+        for (verb, action_description) in actions {
+            let http_route = &action_description.action.http_route;
+
+            for (open_api_route, omitted_keys) in http_route.get_open_api_routes() {
+                if !described_routes.insert((open_api_route.clone(), verb.as_str())) {
+                    let operation = result
+                        .get_mut(open_api_route.as_str())
+                        .and_then(|path_item| path_item.get_mut(verb.as_str()));
+
+                    if let Some(operation) = operation {
+                        operation.description.push_str(&format!(
+                            "\n\nRoute {} is handled by this path as well. It is not described separately.",
+                            route
+                        ));
+                    }
+
+                    continue;
+                }
+
+                let operation = super::verb_description::build(
+                    http_route,
+                    &omitted_keys,
+                    action_description,
+                    controllers,
+                );
+
+                match result.get_mut(open_api_route.as_str()) {
+                    Some(path_item) => path_item.insert(verb.as_str(), operation),
+                    None => {
+                        let mut path_item = OpenApiPathItem::new();
+                        path_item.insert(verb.as_str(), operation);
+                        result.insert(open_api_route, path_item);
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
@@ -0,0 +1,14 @@
+use crate::{
+    controllers::documentation::{HttpEnumStructure, HttpSimpleType, InputStructure},
+    swagger::open_api::OpenApiSchema,
+};
+
+pub fn build_array_input_param(simple_type: &HttpSimpleType) -> OpenApiSchema {
+    OpenApiSchema::array_of(OpenApiSchema::of_type(simple_type.as_swagger_type()))
+}
+
+pub fn build_array_enum_case(enum_structure: &HttpEnumStructure) -> OpenApiSchema {
+    OpenApiSchema::array_of(OpenApiSchema::reference(
+        enum_structure.get_struct_id().as_str(),
+    ))
+}
@@ -0,0 +1,121 @@
+use crate::{
+    controllers::{
+        documentation::{
+            in_parameters::HttpInputParameter, ArrayElement, HttpDataType, InputStructure,
+        },
+        HttpRoute, HttpRouteConstraint, HttpRouteKey,
+    },
+    swagger::open_api::{OpenApiParameter, OpenApiSchema},
+};
+
+pub fn build_query_input_param(
+    http_route: &HttpRoute,
+    input_param: &HttpInputParameter,
+) -> OpenApiParameter {
+    let route_key = if input_param.source.is_path() {
+        http_route.get_key(&input_param.field.name)
+    } else {
+        None
+    };
+
+    let mut schema = match route_key.and_then(|key| key.constraint.as_ref()) {
+        Some(constraint) => build_route_constraint(constraint),
+        None => build_data_type(&input_param.field.data_type),
+    };
+
+    if let Some(default_value) = route_key.and_then(|key| key.default_value.as_ref()) {
+        schema.default = Some(build_default_value(&schema, default_value));
+    }
+
+    OpenApiParameter {
+        location: input_param.source.as_str().to_string(),
+        name: input_param
+            .field
+            .get_query_field_name()
+            .as_str()
+            .to_string(),
+        description: compile_description(input_param, route_key),
+        schema,
+        // OpenAPI path parameters are always required
+        required: input_param.source.is_path() || input_param.field.required,
+    }
+}
+
+fn build_default_value(schema: &OpenApiSchema, default_value: &str) -> serde_json::Value {
+    let value = match schema.schema_type.as_deref() {
+        Some("integer") => default_value
+            .parse::<i64>()
+            .ok()
+            .map(serde_json::Value::from),
+        Some("number") => default_value
+            .parse::<f64>()
+            .ok()
+            .map(serde_json::Value::from),
+        Some("boolean") => default_value
+            .parse::<bool>()
+            .ok()
+            .map(serde_json::Value::from),
+        _ => None,
+    };
+
+    value.unwrap_or_else(|| serde_json::Value::from(default_value))
+}
+
+fn build_data_type(data_type: &HttpDataType) -> OpenApiSchema {
+    match data_type {
+        HttpDataType::SimpleType(simple_type) => {
+            OpenApiSchema::with_format(simple_type.as_swagger_type(), simple_type.as_format())
+        }
+        HttpDataType::Object(_) => {
+            panic!("Object type is not supported for non body parameter")
+        }
+        HttpDataType::ArrayOf(array_el) => build_array_item_of(array_el),
+        HttpDataType::DictionaryOf(_) => {
+            panic!("Dictionary can not be used as a non body parameter")
+        }
+        HttpDataType::DictionaryOfArray(_) => {
+            panic!("Dictionary of array can not be used as a non body parameter")
+        }
+        HttpDataType::Enum(enum_data) => {
+            OpenApiSchema::reference(enum_data.get_struct_id().as_str())
+        }
+        HttpDataType::None => {
+            panic!("Somehow we have non parameter")
+        }
+    }
+}
+
+fn compile_description(
+    input_param: &HttpInputParameter,
+    route_key: Option<&HttpRouteKey>,
+) -> String {
+    let Some(route_key) = route_key else {
+        return input_param.description.to_string();
+    };
+
+    if route_key.catch_all {
+        return format!(
+            "{} (captures the rest of the path)",
+            input_param.description
+        );
+    }
+
+    input_param.description.to_string()
+}
+
+fn build_route_constraint(constraint: &HttpRouteConstraint) -> OpenApiSchema {
+    let mut result = OpenApiSchema::of_type(constraint.as_swagger_type());
+    result.format = constraint.as_format().map(|itm| itm.to_string());
+    result.pattern = constraint.as_pattern().map(|itm| itm.to_string());
+    result
+}
+
+fn build_array_item_of(array_el: &ArrayElement) -> OpenApiSchema {
+    match array_el {
+        ArrayElement::SimpleType(simple_type) => super::build_array_input_param(simple_type),
+        ArrayElement::Object(_) => {
+            panic!("Array of object type is not supported for non body parameter")
+        }
+        ArrayElement::Enum(enum_data) => super::build_array_enum_case(enum_data),
+    }
+}
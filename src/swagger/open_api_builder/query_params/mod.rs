@@ -1,6 +1,6 @@
 mod array_param;
+mod build_query_input_param;
 mod enum_type;
-mod write_query_input_param;
 pub use array_param::*;
+pub use build_query_input_param::*;
 pub use enum_type::*;
-pub use write_query_input_param::*;
@@ -0,0 +1,157 @@
+use crate::{
+    controllers::{ControllersAuthorization, OAuth2Scope},
+    swagger::open_api::{OpenApiMap, OpenApiOAuthFlow, OpenApiOAuthFlows, OpenApiSecurityScheme},
+};
+
+pub fn build(schemes: &[ControllersAuthorization]) -> OpenApiMap<OpenApiSecurityScheme> {
+    let mut result = OpenApiMap::new();
+
+    for auth in schemes {
+        result.insert(auth.as_openid_str(), build_scheme(auth));
+    }
+
+    result
+}
+
+fn build_scheme(auth: &ControllersAuthorization) -> OpenApiSecurityScheme {
+    match auth {
+        ControllersAuthorization::BasicAuthentication {
+            global: _,
+            global_claims: _,
+        } => OpenApiSecurityScheme {
+            scheme_type: "http".to_string(),
+            scheme: Some("basic".to_string()),
+            ..Default::default()
+        },
+        ControllersAuthorization::ApiKeys {
+            global: _,
+            global_claims: _,
+            location,
+        } => OpenApiSecurityScheme {
+            scheme_type: "apiKey".to_string(),
+            location: Some(location.key_in.as_str().to_string()),
+            name: Some(location.name.to_string()),
+            description: location
+                .prefix
+                .as_ref()
+                .map(|prefix| format!("Value format: {} {{key}}", prefix)),
+            ..Default::default()
+        },
+        ControllersAuthorization::BearerAuthentication {
+            global: _,
+            global_claims: _,
+        } => OpenApiSecurityScheme {
+            scheme_type: "http".to_string(),
+            scheme: Some("bearer".to_string()),
+            ..Default::default()
+        },
+        ControllersAuthorization::OAuth2 {
+            global: _,
+            global_claims: _,
+            settings,
+        } => {
+            let mut flows = OpenApiOAuthFlows::default();
+
+            if let Some(authorization_url) = &settings.authorization_url {
+                flows.authorization_code = Some(OpenApiOAuthFlow {
+                    authorization_url: Some(authorization_url.to_string()),
+                    token_url: settings.token_url.to_string(),
+                    refresh_url: settings.refresh_url.clone(),
+                    scopes: build_scopes(&settings.scopes),
+                });
+            }
+
+            if settings.client_credentials {
+                flows.client_credentials = Some(OpenApiOAuthFlow {
+                    authorization_url: None,
+                    token_url: settings.token_url.to_string(),
+                    refresh_url: settings.refresh_url.clone(),
+                    scopes: build_scopes(&settings.scopes),
+                });
+            }
+
+            OpenApiSecurityScheme {
+                scheme_type: "oauth2".to_string(),
+                flows: Some(flows),
+                ..Default::default()
+            }
+        }
+        ControllersAuthorization::OpenIdConnect {
+            global: _,
+            global_claims: _,
+            settings,
+        } => OpenApiSecurityScheme {
+            scheme_type: "openIdConnect".to_string(),
+            open_id_connect_url: Some(settings.openid_connect_url.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+fn build_scopes(scopes: &[OAuth2Scope]) -> OpenApiMap<String> {
+    let mut result = OpenApiMap::new();
+
+    for scope in scopes {
+        result.insert(scope.name.as_str(), scope.description.to_string());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controllers::{ApiKeyLocation, RequiredClaims};
+
+    use super::*;
+
+    fn create_api_keys(location: ApiKeyLocation) -> ControllersAuthorization {
+        ControllersAuthorization::ApiKeys {
+            global: true,
+            global_claims: RequiredClaims::no_claims(),
+            location,
+        }
+    }
+
+    #[test]
+    fn test_api_key_locations() {
+        let schemes = vec![
+            create_api_keys(
+                ApiKeyLocation::header("Authorization")
+                    .with_scheme_name("HeaderKey")
+                    .with_prefix("ApiKey"),
+            ),
+            create_api_keys(ApiKeyLocation::query("api_key").with_scheme_name("QueryKey")),
+            create_api_keys(ApiKeyLocation::cookie("session").with_scheme_name("CookieKey")),
+            ControllersAuthorization::api_keys(false, RequiredClaims::no_claims()),
+        ];
+
+        let result = serde_json::to_value(build(&schemes)).unwrap();
+
+        assert_eq!(
+            serde_json::json!({
+                "HeaderKey": {
+                    "type": "apiKey",
+                    "in": "header",
+                    "name": "Authorization",
+                    "description": "Value format: ApiKey {key}"
+                },
+                "QueryKey": {
+                    "type": "apiKey",
+                    "in": "query",
+                    "name": "api_key"
+                },
+                "CookieKey": {
+                    "type": "apiKey",
+                    "in": "cookie",
+                    "name": "session"
+                },
+                "ApiKeyAuth": {
+                    "type": "apiKey",
+                    "in": "header",
+                    "name": "X-API-Key"
+                }
+            }),
+            result
+        );
+    }
+}
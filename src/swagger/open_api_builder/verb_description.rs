@@ -0,0 +1,108 @@
+use crate::controllers::documentation::out_results::HttpResult;
+
+use crate::controllers::{ControllersMiddleware, HttpRoute};
+use crate::swagger::open_api::{
+    OpenApiMap, OpenApiMediaType, OpenApiOperation, OpenApiResponse, OpenApiSchema,
+    OpenApiSecurityRequirement,
+};
+
+use super::action_description::SwaggerActionDescription;
+
+pub fn build(
+    http_route: &HttpRoute,
+    omitted_keys: &[&str],
+    action_description: &SwaggerActionDescription,
+    controllers: &ControllersMiddleware,
+) -> OpenApiOperation {
+    let (parameters, request_body) =
+        super::in_parameters::build(http_route, omitted_keys, action_description);
+
+    OpenApiOperation {
+        security: compile_security(action_description, controllers),
+        required_permissions: action_description.action.required_permissions.clone(),
+        tags: vec![action_description.controller_name.to_string()],
+        summary: action_description.summary.to_string(),
+        description: action_description.description.to_string(),
+        parameters,
+        request_body,
+        responses: compile_responses(action_description),
+    }
+}
+
+fn compile_security(
+    action_description: &SwaggerActionDescription,
+    controllers: &ControllersMiddleware,
+) -> Option<Vec<OpenApiSecurityRequirement>> {
+    let requirements = controllers
+        .authorization_map
+        .get_security_requirements(action_description.action)?;
+
+    if requirements.len() == 0 {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(requirements.len());
+
+    for requirement in requirements {
+        if !requirement.scheme.has_scopes() {
+            let mut security_requirement = OpenApiSecurityRequirement::new();
+            security_requirement.insert(requirement.scheme.as_openid_str(), Vec::new());
+            result.push(security_requirement);
+            continue;
+        }
+
+        // Scopes of one requirement are AND'ed, requirements are OR'ed
+        for claim_set in requirement
+            .claims
+            .get_expression()
+            .get_required_claim_sets()
+        {
+            let mut security_requirement = OpenApiSecurityRequirement::new();
+            security_requirement.insert(
+                requirement.scheme.as_openid_str(),
+                claim_set.into_iter().map(|itm| itm.to_string()).collect(),
+            );
+            result.push(security_requirement);
+        }
+    }
+
+    Some(result)
+}
+
+fn compile_responses(action_description: &SwaggerActionDescription) -> OpenApiMap<OpenApiResponse> {
+    let mut responses = OpenApiMap::new();
+
+    for http_result in &action_description.results {
+        let content_type = match action_description
+            .action
+            .result_content_types
+            .get(&http_result.http_code)
+        {
+            Some(content_type) => Some(content_type.as_str()),
+            None => http_result.get_content_type(),
+        };
+
+        responses.insert(
+            http_result.http_code.to_string(),
+            compile_response(http_result, content_type),
+        );
+    }
+
+    responses
+}
+
+fn compile_response(src: &HttpResult, content_type: Option<&str>) -> OpenApiResponse {
+    let mut content = OpenApiMap::new();
+
+    if let Some(content_type) = content_type {
+        let schema = super::http_data_type::build(&src.data_type)
+            .unwrap_or_else(|| OpenApiSchema::with_format("string", "binary"));
+
+        content.insert(content_type, OpenApiMediaType::new(schema));
+    }
+
+    OpenApiResponse {
+        description: src.description.to_string(),
+        content,
+    }
+}
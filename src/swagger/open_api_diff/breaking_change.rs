@@ -0,0 +1,218 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreakingChange {
+    RouteRemoved {
+        path: String,
+    },
+    VerbRemoved {
+        path: String,
+        verb: String,
+    },
+    RequiredParameterAdded {
+        path: String,
+        verb: String,
+        parameter: String,
+    },
+    ParameterTypeChanged {
+        path: String,
+        verb: String,
+        parameter: String,
+        old_type: String,
+        new_type: String,
+    },
+    ResponseCodeRemoved {
+        path: String,
+        verb: String,
+        http_code: String,
+    },
+    /// Body is `request body` or `response <http code>`
+    BodyMediaTypeRemoved {
+        path: String,
+        verb: String,
+        body: String,
+        media_type: String,
+    },
+    BodyTypeChanged {
+        path: String,
+        verb: String,
+        body: String,
+        old_type: String,
+        new_type: String,
+    },
+    BodyFieldRemoved {
+        path: String,
+        verb: String,
+        body: String,
+        field: String,
+    },
+    BodyFieldTypeChanged {
+        path: String,
+        verb: String,
+        body: String,
+        field: String,
+        old_type: String,
+        new_type: String,
+    },
+    SchemaRemoved {
+        schema: String,
+    },
+    SchemaTypeChanged {
+        schema: String,
+        old_type: String,
+        new_type: String,
+    },
+    FieldRemoved {
+        schema: String,
+        field: String,
+    },
+    FieldTypeChanged {
+        schema: String,
+        field: String,
+        old_type: String,
+        new_type: String,
+    },
+    RequiredFieldAdded {
+        schema: String,
+        field: String,
+    },
+    EnumCaseRemoved {
+        schema: String,
+        case: String,
+    },
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BreakingChange::RouteRemoved { path } => write!(f, "Route {} is removed", path),
+            BreakingChange::VerbRemoved { path, verb } => {
+                write!(f, "[{}] {} is removed", verb.to_uppercase(), path)
+            }
+            BreakingChange::RequiredParameterAdded {
+                path,
+                verb,
+                parameter,
+            } => write!(
+                f,
+                "[{}] {}: required parameter {} is added",
+                verb.to_uppercase(),
+                path,
+                parameter
+            ),
+            BreakingChange::ParameterTypeChanged {
+                path,
+                verb,
+                parameter,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "[{}] {}: parameter {} type is changed from {} to {}",
+                verb.to_uppercase(),
+                path,
+                parameter,
+                old_type,
+                new_type
+            ),
+            BreakingChange::ResponseCodeRemoved {
+                path,
+                verb,
+                http_code,
+            } => write!(
+                f,
+                "[{}] {}: response {} is removed",
+                verb.to_uppercase(),
+                path,
+                http_code
+            ),
+            BreakingChange::BodyMediaTypeRemoved {
+                path,
+                verb,
+                body,
+                media_type,
+            } => write!(
+                f,
+                "[{}] {}: {} media type {} is removed",
+                verb.to_uppercase(),
+                path,
+                body,
+                media_type
+            ),
+            BreakingChange::BodyTypeChanged {
+                path,
+                verb,
+                body,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "[{}] {}: {} type is changed from {} to {}",
+                verb.to_uppercase(),
+                path,
+                body,
+                old_type,
+                new_type
+            ),
+            BreakingChange::BodyFieldRemoved {
+                path,
+                verb,
+                body,
+                field,
+            } => write!(
+                f,
+                "[{}] {}: {} field {} is removed",
+                verb.to_uppercase(),
+                path,
+                body,
+                field
+            ),
+            BreakingChange::BodyFieldTypeChanged {
+                path,
+                verb,
+                body,
+                field,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "[{}] {}: {} field {} type is changed from {} to {}",
+                verb.to_uppercase(),
+                path,
+                body,
+                field,
+                old_type,
+                new_type
+            ),
+            BreakingChange::SchemaRemoved { schema } => write!(f, "Model {} is removed", schema),
+            BreakingChange::SchemaTypeChanged {
+                schema,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "Model {} type is changed from {} to {}",
+                schema, old_type, new_type
+            ),
+            BreakingChange::FieldRemoved { schema, field } => {
+                write!(f, "Model {}: field {} is removed", schema, field)
+            }
+            BreakingChange::FieldTypeChanged {
+                schema,
+                field,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "Model {}: field {} type is changed from {} to {}",
+                schema, field, old_type, new_type
+            ),
+            BreakingChange::RequiredFieldAdded { schema, field } => {
+                write!(f, "Model {}: required field {} is added", schema, field)
+            }
+            BreakingChange::EnumCaseRemoved { schema, case } => {
+                write!(f, "Enum {}: case {} is removed", schema, case)
+            }
+        }
+    }
+}
@@ -0,0 +1,990 @@
+use std::{collections::HashSet, fmt};
+
+use crate::swagger::open_api::{
+    OpenApiDocument, OpenApiMap, OpenApiMediaType, OpenApiOperation, OpenApiSchema,
+};
+
+use super::BreakingChange;
+
+const SCHEMA_REFERENCE_PREFIX: &str = "#/components/schemas/";
+
+/// Changes of the new document which break clients built against the old one. Compatible additions are not reported.
+/// Model changes are judged by where the old document uses the model: in requests, in responses or both.
+///
+/// Can be used as a test gate against the committed spec:
+/// `assert!(!diff.has_breaking_changes(), "{}", diff)`
+#[derive(Debug, Clone)]
+pub struct OpenApiDiff {
+    pub breaking_changes: Vec<BreakingChange>,
+}
+
+impl OpenApiDiff {
+    pub fn compare(old: &OpenApiDocument, new: &OpenApiDocument) -> Self {
+        let mut breaking_changes = Vec::new();
+
+        compare_paths(old, new, &mut breaking_changes);
+        compare_schemas(old, new, &mut breaking_changes);
+
+        Self { breaking_changes }
+    }
+
+    pub fn has_breaking_changes(&self) -> bool {
+        self.breaking_changes.len() > 0
+    }
+}
+
+impl fmt::Display for OpenApiDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.breaking_changes.len() == 0 {
+            return writeln!(f, "No breaking changes");
+        }
+
+        writeln!(f, "Breaking changes: {}", self.breaking_changes.len())?;
+
+        for breaking_change in &self.breaking_changes {
+            writeln!(f, " - {}", breaking_change)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn compare_paths(old: &OpenApiDocument, new: &OpenApiDocument, result: &mut Vec<BreakingChange>) {
+    for (path, old_path_item) in old.paths.iter() {
+        let Some(new_path_item) = new.paths.get(path) else {
+            result.push(BreakingChange::RouteRemoved {
+                path: path.to_string(),
+            });
+            continue;
+        };
+
+        for (verb, old_operation) in old_path_item.iter() {
+            match new_path_item.get(verb) {
+                Some(new_operation) => {
+                    compare_operations(path, verb, old_operation, new_operation, result)
+                }
+                None => result.push(BreakingChange::VerbRemoved {
+                    path: path.to_string(),
+                    verb: verb.to_string(),
+                }),
+            }
+        }
+    }
+}
+
+fn compare_operations(
+    path: &str,
+    verb: &str,
+    old: &OpenApiOperation,
+    new: &OpenApiOperation,
+    result: &mut Vec<BreakingChange>,
+) {
+    for new_parameter in &new.parameters {
+        let old_parameter = old
+            .parameters
+            .iter()
+            .find(|itm| itm.name == new_parameter.name && itm.location == new_parameter.location);
+
+        let parameter = format!("{} ({})", new_parameter.name, new_parameter.location);
+
+        if new_parameter.required && !old_parameter.map(|itm| itm.required).unwrap_or(false) {
+            result.push(BreakingChange::RequiredParameterAdded {
+                path: path.to_string(),
+                verb: verb.to_string(),
+                parameter: parameter.clone(),
+            });
+        }
+
+        if let Some(old_parameter) = old_parameter {
+            let old_type = describe_schema(&old_parameter.schema);
+            let new_type = describe_schema(&new_parameter.schema);
+
+            if old_type != new_type {
+                result.push(BreakingChange::ParameterTypeChanged {
+                    path: path.to_string(),
+                    verb: verb.to_string(),
+                    parameter,
+                    old_type,
+                    new_type,
+                });
+            }
+        }
+    }
+
+    if let Some(new_request_body) = &new.request_body {
+        match &old.request_body {
+            Some(old_request_body) => compare_content(
+                path,
+                verb,
+                "request body",
+                &old_request_body.content,
+                &new_request_body.content,
+                result,
+            ),
+            None => {
+                if new_request_body.required == Some(true) {
+                    result.push(BreakingChange::RequiredParameterAdded {
+                        path: path.to_string(),
+                        verb: verb.to_string(),
+                        parameter: "request body".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    for (http_code, old_response) in old.responses.iter() {
+        match new.responses.get(http_code) {
+            Some(new_response) => compare_content(
+                path,
+                verb,
+                format!("response {}", http_code).as_str(),
+                &old_response.content,
+                &new_response.content,
+                result,
+            ),
+            None => result.push(BreakingChange::ResponseCodeRemoved {
+                path: path.to_string(),
+                verb: verb.to_string(),
+                http_code: http_code.to_string(),
+            }),
+        }
+    }
+}
+
+fn compare_content(
+    path: &str,
+    verb: &str,
+    body: &str,
+    old: &OpenApiMap<OpenApiMediaType>,
+    new: &OpenApiMap<OpenApiMediaType>,
+    result: &mut Vec<BreakingChange>,
+) {
+    for (media_type, old_media_type) in old.iter() {
+        let Some(new_media_type) = new.get(media_type) else {
+            result.push(BreakingChange::BodyMediaTypeRemoved {
+                path: path.to_string(),
+                verb: verb.to_string(),
+                body: body.to_string(),
+                media_type: media_type.to_string(),
+            });
+            continue;
+        };
+
+        let old_type = describe_schema(&old_media_type.schema);
+        let new_type = describe_schema(&new_media_type.schema);
+
+        if old_type != new_type {
+            result.push(BreakingChange::BodyTypeChanged {
+                path: path.to_string(),
+                verb: verb.to_string(),
+                body: body.to_string(),
+                old_type,
+                new_type,
+            });
+            continue;
+        }
+
+        // Models are compared by compare_schemas. Only inline objects, like multi field bodies, are compared here
+        if old_media_type.schema.reference.is_some() {
+            continue;
+        }
+
+        let Some(old_properties) = &old_media_type.schema.properties else {
+            continue;
+        };
+
+        for (field, old_field) in old_properties.iter() {
+            let new_field = new_media_type
+                .schema
+                .properties
+                .as_ref()
+                .and_then(|properties| properties.get(field));
+
+            let Some(new_field) = new_field else {
+                result.push(BreakingChange::BodyFieldRemoved {
+                    path: path.to_string(),
+                    verb: verb.to_string(),
+                    body: body.to_string(),
+                    field: field.to_string(),
+                });
+                continue;
+            };
+
+            let old_type = describe_schema(old_field);
+            let new_type = describe_schema(new_field);
+
+            if old_type != new_type {
+                result.push(BreakingChange::BodyFieldTypeChanged {
+                    path: path.to_string(),
+                    verb: verb.to_string(),
+                    body: body.to_string(),
+                    field: field.to_string(),
+                    old_type,
+                    new_type,
+                });
+            }
+        }
+    }
+}
+
+/// Where clients built against the document meet the model
+#[derive(Debug, Clone, Copy)]
+struct SchemaUsage {
+    in_request: bool,
+    in_response: bool,
+}
+
+fn compare_schemas(old: &OpenApiDocument, new: &OpenApiDocument, result: &mut Vec<BreakingChange>) {
+    let mut request_schemas = HashSet::new();
+    let mut response_schemas = HashSet::new();
+
+    for (_, path_item) in old.paths.iter() {
+        for (_, operation) in path_item.iter() {
+            for parameter in &operation.parameters {
+                collect_references(old, &parameter.schema, &mut request_schemas);
+            }
+
+            if let Some(request_body) = &operation.request_body {
+                for (_, media_type) in request_body.content.iter() {
+                    collect_references(old, &media_type.schema, &mut request_schemas);
+                }
+            }
+
+            for (_, response) in operation.responses.iter() {
+                for (_, media_type) in response.content.iter() {
+                    collect_references(old, &media_type.schema, &mut response_schemas);
+                }
+            }
+        }
+    }
+
+    for (name, old_schema) in old.components.schemas.iter() {
+        let mut usage = SchemaUsage {
+            in_request: request_schemas.contains(name),
+            in_response: response_schemas.contains(name),
+        };
+
+        // Model is not referenced by any operation - we can not tell how clients use it
+        if !usage.in_request && !usage.in_response {
+            usage.in_request = true;
+            usage.in_response = true;
+        }
+
+        match new.components.schemas.get(name) {
+            Some(new_schema) => compare_schema(name, usage, old_schema, new_schema, result),
+            None => result.push(BreakingChange::SchemaRemoved {
+                schema: name.to_string(),
+            }),
+        }
+    }
+}
+
+/// Collects models the schema references, including models referenced by their fields
+fn collect_references<'s>(
+    document: &'s OpenApiDocument,
+    schema: &'s OpenApiSchema,
+    result: &mut HashSet<&'s str>,
+) {
+    if let Some(reference) = &schema.reference {
+        let name = reference.trim_start_matches(SCHEMA_REFERENCE_PREFIX);
+
+        if result.insert(name) {
+            if let Some(referenced_schema) = document.components.schemas.get(name) {
+                collect_references(document, referenced_schema, result);
+            }
+        }
+    }
+
+    if let Some(properties) = &schema.properties {
+        for (_, field) in properties.iter() {
+            collect_references(document, field, result);
+        }
+    }
+
+    if let Some(items) = &schema.items {
+        collect_references(document, items, result);
+    }
+
+    if let Some(additional_properties) = &schema.additional_properties {
+        collect_references(document, additional_properties, result);
+    }
+}
+
+/// Removed field breaks clients which read the model. Added required field breaks clients which send it.
+fn compare_schema(
+    name: &str,
+    usage: SchemaUsage,
+    old: &OpenApiSchema,
+    new: &OpenApiSchema,
+    result: &mut Vec<BreakingChange>,
+) {
+    let old_type = describe_schema(old);
+    let new_type = describe_schema(new);
+
+    if old_type != new_type {
+        result.push(BreakingChange::SchemaTypeChanged {
+            schema: name.to_string(),
+            old_type,
+            new_type,
+        });
+    }
+
+    for case in &old.enum_values {
+        if !new.enum_values.contains(case) {
+            result.push(BreakingChange::EnumCaseRemoved {
+                schema: name.to_string(),
+                case: match case {
+                    serde_json::Value::String(value) => value.to_string(),
+                    _ => case.to_string(),
+                },
+            });
+        }
+    }
+
+    if let Some(old_properties) = &old.properties {
+        for (field, old_field) in old_properties.iter() {
+            let new_field = new
+                .properties
+                .as_ref()
+                .and_then(|properties| properties.get(field));
+
+            let Some(new_field) = new_field else {
+                if usage.in_response {
+                    result.push(BreakingChange::FieldRemoved {
+                        schema: name.to_string(),
+                        field: field.to_string(),
+                    });
+                }
+                continue;
+            };
+
+            let old_type = describe_schema(old_field);
+            let new_type = describe_schema(new_field);
+
+            if old_type != new_type {
+                result.push(BreakingChange::FieldTypeChanged {
+                    schema: name.to_string(),
+                    field: field.to_string(),
+                    old_type,
+                    new_type,
+                });
+            }
+        }
+    }
+
+    if !usage.in_request {
+        return;
+    }
+
+    for field in &new.required {
+        if !old.required.contains(field) {
+            result.push(BreakingChange::RequiredFieldAdded {
+                schema: name.to_string(),
+                field: field.to_string(),
+            });
+        }
+    }
+}
+
+fn describe_schema(schema: &OpenApiSchema) -> String {
+    if let Some(reference) = &schema.reference {
+        return reference
+            .trim_start_matches(SCHEMA_REFERENCE_PREFIX)
+            .to_string();
+    }
+
+    if let Some(items) = &schema.items {
+        return format!("array of {}", describe_schema(items));
+    }
+
+    if let Some(additional_properties) = &schema.additional_properties {
+        return format!("dictionary of {}", describe_schema(additional_properties));
+    }
+
+    let schema_type = schema.schema_type.as_deref().unwrap_or("any");
+
+    match &schema.format {
+        Some(format) => format!("{}({})", schema_type, format),
+        None => schema_type.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::swagger::open_api::{
+        OpenApiParameter, OpenApiPathItem, OpenApiRequestBody, OpenApiResponse,
+    };
+
+    fn create_operation(parameters: Vec<OpenApiParameter>, codes: &[&str]) -> OpenApiOperation {
+        let mut responses = OpenApiMap::new();
+
+        for code in codes {
+            responses.insert(
+                *code,
+                OpenApiResponse {
+                    description: "Result".to_string(),
+                    content: OpenApiMap::new(),
+                },
+            );
+        }
+
+        OpenApiOperation {
+            security: None,
+            required_permissions: vec![],
+            tags: vec!["Items".to_string()],
+            summary: "Items".to_string(),
+            description: "Items".to_string(),
+            parameters,
+            request_body: None,
+            responses,
+        }
+    }
+
+    fn create_parameter(name: &str, schema_type: &str, required: bool) -> OpenApiParameter {
+        OpenApiParameter {
+            location: "query".to_string(),
+            name: name.to_string(),
+            description: name.to_string(),
+            schema: OpenApiSchema::of_type(schema_type),
+            required,
+        }
+    }
+
+    fn create_document(
+        operations: Vec<(&str, &str, OpenApiOperation)>,
+        schemas: Vec<(&str, OpenApiSchema)>,
+    ) -> OpenApiDocument {
+        let mut document = OpenApiDocument::new("Test", "1.0.0");
+
+        for (path, verb, operation) in operations {
+            if !document.paths.contains_key(path) {
+                document.paths.insert(path, OpenApiPathItem::new());
+            }
+
+            document
+                .paths
+                .get_mut(path)
+                .unwrap()
+                .insert(verb, operation);
+        }
+
+        for (name, schema) in schemas {
+            document.components.schemas.insert(name, schema);
+        }
+
+        document
+    }
+
+    fn create_model(fields: &[(&str, &str)], required: &[&str]) -> OpenApiSchema {
+        let mut properties = OpenApiMap::new();
+
+        for (name, schema_type) in fields {
+            properties.insert(*name, OpenApiSchema::of_type(schema_type));
+        }
+
+        let mut result = OpenApiSchema::object(properties);
+        result.required = required.iter().map(|itm| itm.to_string()).collect();
+        result
+    }
+
+    fn create_enum(cases: &[i64]) -> OpenApiSchema {
+        let mut result = OpenApiSchema::of_type("integer");
+        result.enum_values = cases.iter().map(|itm| Value::from(*itm)).collect();
+        result
+    }
+
+    #[test]
+    fn test_same_document_has_no_breaking_changes() {
+        let document = create_document(
+            vec![(
+                "/api/items",
+                "get",
+                create_operation(vec![create_parameter("id", "string", true)], &["200"]),
+            )],
+            vec![
+                ("Item", create_model(&[("id", "string")], &["id"])),
+                ("ItemType", create_enum(&[0, 1])),
+            ],
+        );
+
+        let parsed = OpenApiDocument::from_json(&document.to_json()).unwrap();
+
+        let diff = OpenApiDiff::compare(&document, &parsed);
+
+        assert!(!diff.has_breaking_changes(), "{}", diff);
+        assert_eq!(diff.to_string(), "No breaking changes\n");
+    }
+
+    #[test]
+    fn test_routes_and_operations_changes() {
+        let old = create_document(
+            vec![
+                (
+                    "/api/items",
+                    "get",
+                    create_operation(
+                        vec![
+                            create_parameter("skip", "integer", false),
+                            create_parameter("take", "integer", false),
+                        ],
+                        &["200", "404"],
+                    ),
+                ),
+                ("/api/items", "delete", create_operation(vec![], &["200"])),
+                ("/api/legacy", "get", create_operation(vec![], &["200"])),
+            ],
+            vec![],
+        );
+
+        let new = create_document(
+            vec![(
+                "/api/items",
+                "get",
+                create_operation(
+                    vec![
+                        create_parameter("skip", "integer", true),
+                        create_parameter("take", "string", false),
+                        create_parameter("filter", "string", false),
+                        create_parameter("tenant", "string", true),
+                    ],
+                    &["200", "500"],
+                ),
+            )],
+            vec![],
+        );
+
+        let diff = OpenApiDiff::compare(&old, &new);
+
+        assert_eq!(
+            diff.breaking_changes,
+            vec![
+                BreakingChange::RequiredParameterAdded {
+                    path: "/api/items".to_string(),
+                    verb: "get".to_string(),
+                    parameter: "skip (query)".to_string(),
+                },
+                BreakingChange::ParameterTypeChanged {
+                    path: "/api/items".to_string(),
+                    verb: "get".to_string(),
+                    parameter: "take (query)".to_string(),
+                    old_type: "integer".to_string(),
+                    new_type: "string".to_string(),
+                },
+                BreakingChange::RequiredParameterAdded {
+                    path: "/api/items".to_string(),
+                    verb: "get".to_string(),
+                    parameter: "tenant (query)".to_string(),
+                },
+                BreakingChange::ResponseCodeRemoved {
+                    path: "/api/items".to_string(),
+                    verb: "get".to_string(),
+                    http_code: "404".to_string(),
+                },
+                BreakingChange::VerbRemoved {
+                    path: "/api/items".to_string(),
+                    verb: "delete".to_string(),
+                },
+                BreakingChange::RouteRemoved {
+                    path: "/api/legacy".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_changes() {
+        let old = create_document(
+            vec![],
+            vec![
+                (
+                    "Item",
+                    create_model(
+                        &[("id", "string"), ("price", "number"), ("name", "string")],
+                        &["id"],
+                    ),
+                ),
+                ("ItemType", create_enum(&[0, 1, 2])),
+                ("Legacy", create_model(&[], &[])),
+            ],
+        );
+
+        let new = create_document(
+            vec![],
+            vec![
+                (
+                    "Item",
+                    create_model(
+                        &[("id", "string"), ("price", "string"), ("count", "integer")],
+                        &["id", "count"],
+                    ),
+                ),
+                ("ItemType", create_enum(&[0, 2])),
+            ],
+        );
+
+        let diff = OpenApiDiff::compare(&old, &new);
+
+        assert_eq!(
+            diff.breaking_changes,
+            vec![
+                BreakingChange::FieldTypeChanged {
+                    schema: "Item".to_string(),
+                    field: "price".to_string(),
+                    old_type: "number".to_string(),
+                    new_type: "string".to_string(),
+                },
+                BreakingChange::FieldRemoved {
+                    schema: "Item".to_string(),
+                    field: "name".to_string(),
+                },
+                BreakingChange::RequiredFieldAdded {
+                    schema: "Item".to_string(),
+                    field: "count".to_string(),
+                },
+                BreakingChange::EnumCaseRemoved {
+                    schema: "ItemType".to_string(),
+                    case: "1".to_string(),
+                },
+                BreakingChange::SchemaRemoved {
+                    schema: "Legacy".to_string(),
+                },
+            ]
+        );
+
+        assert_eq!(
+            diff.to_string(),
+            "Breaking changes: 5\n - Model Item: field price type is changed from number to string\n - Model Item: field name is removed\n - Model Item: required field count is added\n - Enum ItemType: case 1 is removed\n - Model Legacy is removed\n"
+        );
+    }
+
+    fn create_body_operation(
+        request_body: OpenApiSchema,
+        response: (&str, OpenApiSchema),
+    ) -> OpenApiOperation {
+        let mut result = create_operation(vec![], &[]);
+
+        let mut content = OpenApiMap::new();
+        content.insert("application/json", OpenApiMediaType::new(request_body));
+
+        result.request_body = Some(OpenApiRequestBody {
+            description: None,
+            required: Some(true),
+            content,
+        });
+
+        let (media_type, schema) = response;
+        let mut content = OpenApiMap::new();
+        content.insert(media_type, OpenApiMediaType::new(schema));
+
+        result.responses.insert(
+            "200",
+            OpenApiResponse {
+                description: "Result".to_string(),
+                content,
+            },
+        );
+
+        result
+    }
+
+    #[test]
+    fn test_request_body_changes() {
+        let old = create_document(
+            vec![
+                (
+                    "/api/items",
+                    "post",
+                    create_body_operation(
+                        create_model(
+                            &[("name", "string"), ("price", "number"), ("note", "string")],
+                            &[],
+                        ),
+                        ("application/json", OpenApiSchema::reference("Item")),
+                    ),
+                ),
+                (
+                    "/api/items",
+                    "put",
+                    create_body_operation(
+                        OpenApiSchema::reference("Item"),
+                        ("application/json", OpenApiSchema::reference("Item")),
+                    ),
+                ),
+            ],
+            vec![],
+        );
+
+        let new = create_document(
+            vec![
+                (
+                    "/api/items",
+                    "post",
+                    create_body_operation(
+                        create_model(
+                            &[
+                                ("name", "string"),
+                                ("price", "string"),
+                                ("count", "integer"),
+                            ],
+                            &[],
+                        ),
+                        ("application/json", OpenApiSchema::reference("Item")),
+                    ),
+                ),
+                (
+                    "/api/items",
+                    "put",
+                    create_body_operation(
+                        OpenApiSchema::reference("UpdateItem"),
+                        ("application/json", OpenApiSchema::reference("Item")),
+                    ),
+                ),
+            ],
+            vec![],
+        );
+
+        let diff = OpenApiDiff::compare(&old, &new);
+
+        assert_eq!(
+            diff.breaking_changes,
+            vec![
+                BreakingChange::BodyFieldTypeChanged {
+                    path: "/api/items".to_string(),
+                    verb: "post".to_string(),
+                    body: "request body".to_string(),
+                    field: "price".to_string(),
+                    old_type: "number".to_string(),
+                    new_type: "string".to_string(),
+                },
+                BreakingChange::BodyFieldRemoved {
+                    path: "/api/items".to_string(),
+                    verb: "post".to_string(),
+                    body: "request body".to_string(),
+                    field: "note".to_string(),
+                },
+                BreakingChange::BodyTypeChanged {
+                    path: "/api/items".to_string(),
+                    verb: "put".to_string(),
+                    body: "request body".to_string(),
+                    old_type: "Item".to_string(),
+                    new_type: "UpdateItem".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_response_body_changes() {
+        let old = create_document(
+            vec![
+                (
+                    "/api/items",
+                    "get",
+                    create_body_operation(
+                        create_model(&[], &[]),
+                        (
+                            "application/json",
+                            OpenApiSchema::array_of(OpenApiSchema::reference("Item")),
+                        ),
+                    ),
+                ),
+                (
+                    "/api/items/{id}",
+                    "get",
+                    create_body_operation(
+                        create_model(&[], &[]),
+                        (
+                            "application/json",
+                            create_model(&[("id", "string"), ("total", "integer")], &[]),
+                        ),
+                    ),
+                ),
+                (
+                    "/api/items/{id}/image",
+                    "get",
+                    create_body_operation(
+                        create_model(&[], &[]),
+                        ("image/png", OpenApiSchema::with_format("string", "binary")),
+                    ),
+                ),
+            ],
+            vec![],
+        );
+
+        let new = create_document(
+            vec![
+                (
+                    "/api/items",
+                    "get",
+                    create_body_operation(
+                        create_model(&[], &[]),
+                        ("application/json", OpenApiSchema::reference("Item")),
+                    ),
+                ),
+                (
+                    "/api/items/{id}",
+                    "get",
+                    create_body_operation(
+                        create_model(&[], &[]),
+                        (
+                            "application/json",
+                            create_model(&[("total", "number")], &[]),
+                        ),
+                    ),
+                ),
+                (
+                    "/api/items/{id}/image",
+                    "get",
+                    create_body_operation(
+                        create_model(&[], &[]),
+                        (
+                            "application/octet-stream",
+                            OpenApiSchema::with_format("string", "binary"),
+                        ),
+                    ),
+                ),
+            ],
+            vec![],
+        );
+
+        let diff = OpenApiDiff::compare(&old, &new);
+
+        assert_eq!(
+            diff.breaking_changes,
+            vec![
+                BreakingChange::BodyTypeChanged {
+                    path: "/api/items".to_string(),
+                    verb: "get".to_string(),
+                    body: "response 200".to_string(),
+                    old_type: "array of Item".to_string(),
+                    new_type: "Item".to_string(),
+                },
+                BreakingChange::BodyFieldRemoved {
+                    path: "/api/items/{id}".to_string(),
+                    verb: "get".to_string(),
+                    body: "response 200".to_string(),
+                    field: "id".to_string(),
+                },
+                BreakingChange::BodyFieldTypeChanged {
+                    path: "/api/items/{id}".to_string(),
+                    verb: "get".to_string(),
+                    body: "response 200".to_string(),
+                    field: "total".to_string(),
+                    old_type: "integer".to_string(),
+                    new_type: "number".to_string(),
+                },
+                BreakingChange::BodyMediaTypeRemoved {
+                    path: "/api/items/{id}/image".to_string(),
+                    verb: "get".to_string(),
+                    body: "response 200".to_string(),
+                    media_type: "image/png".to_string(),
+                },
+            ]
+        );
+
+        assert_eq!(
+            diff.breaking_changes[0].to_string(),
+            "[GET] /api/items: response 200 type is changed from array of Item to Item"
+        );
+    }
+
+    #[test]
+    fn test_response_model_changes() {
+        let operation = create_body_operation(
+            create_model(&[], &[]),
+            (
+                "application/json",
+                OpenApiSchema::array_of(OpenApiSchema::reference("Item")),
+            ),
+        );
+
+        let old = create_document(
+            vec![("/api/items", "get", operation.clone())],
+            vec![(
+                "Item",
+                create_model(&[("id", "string"), ("name", "string")], &["id"]),
+            )],
+        );
+
+        let new = create_document(
+            vec![("/api/items", "get", operation)],
+            vec![(
+                "Item",
+                create_model(&[("id", "string"), ("count", "integer")], &["id", "count"]),
+            )],
+        );
+
+        let diff = OpenApiDiff::compare(&old, &new);
+
+        // Clients do not send the model - new required field is compatible
+        assert_eq!(
+            diff.breaking_changes,
+            vec![BreakingChange::FieldRemoved {
+                schema: "Item".to_string(),
+                field: "name".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_request_model_changes() {
+        let operation = create_body_operation(
+            OpenApiSchema::reference("CreateItem"),
+            ("text/plain", OpenApiSchema::of_type("string")),
+        );
+
+        let mut old_model = create_model(&[("name", "string"), ("note", "string")], &[]);
+        old_model
+            .properties
+            .as_mut()
+            .unwrap()
+            .insert("address", OpenApiSchema::reference("Address"));
+
+        let old = create_document(
+            vec![("/api/items", "post", operation.clone())],
+            vec![
+                ("CreateItem", old_model),
+                ("Address", create_model(&[("city", "string")], &[])),
+            ],
+        );
+
+        let mut new_model = create_model(&[("name", "string"), ("count", "integer")], &["count"]);
+        new_model
+            .properties
+            .as_mut()
+            .unwrap()
+            .insert("address", OpenApiSchema::reference("Address"));
+
+        let new = create_document(
+            vec![("/api/items", "post", operation)],
+            vec![
+                ("CreateItem", new_model),
+                (
+                    "Address",
+                    create_model(&[("city", "string"), ("zip", "string")], &["zip"]),
+                ),
+            ],
+        );
+
+        let diff = OpenApiDiff::compare(&old, &new);
+
+        // Server does not send the model back - removed field is compatible
+        assert_eq!(
+            diff.breaking_changes,
+            vec![
+                BreakingChange::RequiredFieldAdded {
+                    schema: "CreateItem".to_string(),
+                    field: "count".to_string(),
+                },
+                BreakingChange::RequiredFieldAdded {
+                    schema: "Address".to_string(),
+                    field: "zip".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,4 @@
+mod breaking_change;
+mod compare;
+pub use breaking_change::*;
+pub use compare::*;
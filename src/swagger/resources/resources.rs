@@ -1,4 +1,5 @@
 pub static INDEX_PAGE: &[u8] = std::include_bytes!("index.html");
+pub static OAUTH2_REDIRECT_PAGE: &[u8] = std::include_bytes!("oauth2-redirect.html");
 pub static SWAGGER_UI_CSS: &[u8] = std::include_bytes!("swagger-ui.css");
 
 pub static SWAGGER_UI_BUNDLE_JS: &[u8] = std::include_bytes!("swagger-ui-bundle.js");
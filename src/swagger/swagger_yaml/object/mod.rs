@@ -1,2 +0,0 @@
-mod reference_to_object;
-pub use reference_to_object::*;